@@ -0,0 +1,499 @@
+// plays thousands of seeded games with a reference agent and reports how they went,
+// for charting the effect of balance changes. run with --help for the options
+use dungeon_raid_core::game::agent::{Agent, AgentAction, GreedyAgent, RandomAgent};
+use dungeon_raid_core::game::config::GameConfig;
+use dungeon_raid_core::game::ruleset::Ruleset;
+use dungeon_raid_core::game::run_summary::CauseOfDeath;
+use dungeon_raid_core::game::runner;
+use dungeon_raid_core::game::{AbilityType, Game, SpecialType};
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const USAGE: &str = "usage: balance_simulator [options]
+
+options:
+  --games N         number of games to play (default 1000)
+  --seed S          seed of the first game, game i uses S + i (default 0)
+  --agent NAME      random or greedy (default random)
+  --ruleset PATH    a .toml or .json ruleset (default: the built-in ruleset)
+  --threads N       worker threads (default: available parallelism)
+  --max-turns N     stop a game that's still going after N turns (default 10000)
+  --bucket N        width of the survival turn histogram buckets (default 10)
+  --format FORMAT   json or csv (default json)
+  --out PATH        write the report here instead of stdout
+  --help            print this message";
+
+const DEFAULT_GAMES: usize = 1000;
+const DEFAULT_MAX_TURNS: usize = 10000;
+const DEFAULT_BUCKET: usize = 10;
+
+// deaths with no special attacking are put down to regular enemies
+const REGULAR_ENEMIES: &str = "RegularEnemies";
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum AgentKind {
+    Random,
+    Greedy,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Format {
+    Json,
+    Csv,
+}
+
+struct Options {
+    games: usize,
+    seed: u64,
+    agent: AgentKind,
+    ruleset: Ruleset,
+    threads: usize,
+    max_turns: usize,
+    bucket: usize,
+    format: Format,
+    out: Option<String>,
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} needs a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got \"{}\"", flag, value))
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml_ruleset(contents: &str) -> Result<Ruleset, String> {
+    Ruleset::from_toml_str(contents).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_toml_ruleset(_contents: &str) -> Result<Ruleset, String> {
+    Err(String::from(
+        "reading .toml rulesets needs the toml feature",
+    ))
+}
+
+#[cfg(feature = "json")]
+fn parse_json_ruleset(contents: &str) -> Result<Ruleset, String> {
+    Ruleset::from_json_str(contents).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "json"))]
+fn parse_json_ruleset(_contents: &str) -> Result<Ruleset, String> {
+    Err(String::from(
+        "reading .json rulesets needs the json feature",
+    ))
+}
+
+fn load_ruleset(path: &str) -> Result<Ruleset, String> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    let result = if path.ends_with(".toml") {
+        parse_toml_ruleset(&contents)
+    } else if path.ends_with(".json") {
+        parse_json_ruleset(&contents)
+    } else {
+        Err(String::from("rulesets must be .toml or .json files"))
+    };
+    result.map_err(|e| format!("{}: {}", path, e))
+}
+
+// Ok(None) means --help was given
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        games: DEFAULT_GAMES,
+        seed: 0,
+        agent: AgentKind::Random,
+        ruleset: Ruleset::default(),
+        threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        max_turns: DEFAULT_MAX_TURNS,
+        bucket: DEFAULT_BUCKET,
+        format: Format::Json,
+        out: None,
+    };
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--games" => options.games = parse_number(&flag, args.next())?,
+            "--seed" => options.seed = parse_number(&flag, args.next())?,
+            "--threads" => options.threads = parse_number(&flag, args.next())?,
+            "--max-turns" => options.max_turns = parse_number(&flag, args.next())?,
+            "--bucket" => options.bucket = parse_number(&flag, args.next())?,
+            "--agent" => {
+                options.agent = match args.next().as_deref() {
+                    Some("random") => AgentKind::Random,
+                    Some("greedy") => AgentKind::Greedy,
+                    _ => return Err(String::from("--agent must be random or greedy")),
+                }
+            }
+            "--format" => {
+                options.format = match args.next().as_deref() {
+                    Some("json") => Format::Json,
+                    Some("csv") => Format::Csv,
+                    _ => return Err(String::from("--format must be json or csv")),
+                }
+            }
+            "--ruleset" => {
+                let path = args.next().ok_or("--ruleset needs a path")?;
+                options.ruleset = load_ruleset(&path)?;
+            }
+            "--out" => options.out = Some(args.next().ok_or("--out needs a path")?),
+            "--help" | "-h" => return Ok(None),
+            _ => return Err(format!("unknown option \"{}\"", flag)),
+        }
+    }
+    if options.threads == 0 || options.bucket == 0 {
+        return Err(String::from("--threads and --bucket must be above 0"));
+    }
+    Ok(Some(options))
+}
+
+// wraps an agent to count which improvements get offered and which get picked
+struct CountingAgent<'a> {
+    agent: &'a mut dyn Agent,
+    offered: Vec<String>,
+    picked: Vec<String>,
+}
+
+impl Agent for CountingAgent<'_> {
+    fn choose_action(&mut self, game: &Game) -> Option<AgentAction> {
+        let action = self.agent.choose_action(game);
+        if let (Some(set), Some(AgentAction::ChooseImprovements(indeces))) =
+            (game.improvement_choice_set(), &action)
+        {
+            for display in set.displays.iter() {
+                self.offered.push(display.description.clone());
+            }
+            for index in indeces.iter() {
+                if let Some(display) = set.displays.get(*index) {
+                    self.picked.push(display.description.clone());
+                }
+            }
+        }
+        action
+    }
+}
+
+struct GameResult {
+    turns_survived: usize,
+    game_over: bool,
+    level_reached: usize,
+    // the specials that attacked on the killing turn, REGULAR_ENEMIES if none did
+    death_causes: Vec<String>,
+    abilities_cast: Vec<(AbilityType, usize)>,
+    improvements_offered: Vec<String>,
+    improvements_picked: Vec<String>,
+}
+
+fn play_game(options: &Options, seed: u64) -> Result<GameResult, String> {
+    let config = GameConfig::builder()
+        .seed(seed)
+        .ruleset(options.ruleset.clone())
+        .undo_limit(0)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut game = Game::new(config);
+    let mut random_agent;
+    let mut greedy_agent;
+    let agent: &mut dyn Agent = match options.agent {
+        AgentKind::Random => {
+            random_agent = RandomAgent::new(seed);
+            &mut random_agent
+        }
+        AgentKind::Greedy => {
+            greedy_agent = GreedyAgent::new();
+            &mut greedy_agent
+        }
+    };
+    let mut counting_agent = CountingAgent {
+        agent,
+        offered: vec![],
+        picked: vec![],
+    };
+    let result = runner::run_match(&mut game, &mut counting_agent, options.max_turns)
+        .map_err(|e| format!("game with seed {} stopped: {}", seed, e))?;
+    let death_causes = match result.summary.cause_of_death {
+        Some(CauseOfDeath::EnemyAttack { attacking_specials }) => {
+            let mut causes: Vec<String> = vec![];
+            for special_type in attacking_specials.iter() {
+                let cause = format!("{:?}", special_type);
+                if !causes.contains(&cause) {
+                    causes.push(cause);
+                }
+            }
+            if causes.is_empty() {
+                causes.push(String::from(REGULAR_ENEMIES));
+            }
+            causes
+        }
+        Some(CauseOfDeath::KamikazeExplosion) => vec![format!("{:?}", SpecialType::Kamikaze)],
+        Some(CauseOfDeath::NoLegalPath) => vec![format!("{:?}", CauseOfDeath::NoLegalPath)],
+        None => vec![],
+    };
+    Ok(GameResult {
+        turns_survived: result.summary.turns_survived,
+        game_over: result.game_over,
+        level_reached: result.summary.level_ups + 1,
+        death_causes,
+        abilities_cast: result.abilities_cast,
+        improvements_offered: counting_agent.offered,
+        improvements_picked: counting_agent.picked,
+    })
+}
+
+fn play_games(options: &Options) -> Result<Vec<GameResult>, String> {
+    let next_game = AtomicUsize::new(0);
+    let results: Mutex<Vec<(usize, Result<GameResult, String>)>> =
+        Mutex::new(Vec::with_capacity(options.games));
+    std::thread::scope(|scope| {
+        for _ in 0..options.threads {
+            scope.spawn(|| loop {
+                let game_index = next_game.fetch_add(1, Ordering::Relaxed);
+                if game_index >= options.games {
+                    return;
+                }
+                let result = play_game(options, options.seed.wrapping_add(game_index as u64));
+                results
+                    .lock()
+                    .expect("a worker panicked while holding the results")
+                    .push((game_index, result));
+            });
+        }
+    });
+    let mut results = results
+        .into_inner()
+        .expect("a worker panicked while holding the results");
+    // back into seed order so reports don't depend on thread timing
+    results.sort_by_key(|(game_index, _)| *game_index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+struct Report {
+    games: usize,
+    games_over: usize,
+    mean_turns: f64,
+    median_turns: usize,
+    min_turns: usize,
+    max_turns: usize,
+    // bucket start -> games that ended in [start, start + bucket)
+    survival_histogram: BTreeMap<usize, usize>,
+    death_causes: BTreeMap<String, usize>,
+    // description -> (times offered, times picked)
+    improvements: BTreeMap<String, (usize, usize)>,
+    abilities_cast: BTreeMap<String, usize>,
+    mean_level: f64,
+}
+
+impl Report {
+    fn new(results: &[GameResult], bucket: usize) -> Report {
+        let games = results.len();
+        let mut turns: Vec<usize> = results.iter().map(|r| r.turns_survived).collect();
+        turns.sort_unstable();
+        let mut report = Report {
+            games,
+            games_over: results.iter().filter(|r| r.game_over).count(),
+            mean_turns: mean(turns.iter().sum(), games),
+            median_turns: turns.get(games / 2).copied().unwrap_or(0),
+            min_turns: turns.first().copied().unwrap_or(0),
+            max_turns: turns.last().copied().unwrap_or(0),
+            survival_histogram: BTreeMap::new(),
+            death_causes: BTreeMap::new(),
+            improvements: BTreeMap::new(),
+            abilities_cast: BTreeMap::new(),
+            mean_level: mean(results.iter().map(|r| r.level_reached).sum(), games),
+        };
+        for result in results.iter() {
+            *report
+                .survival_histogram
+                .entry(result.turns_survived / bucket * bucket)
+                .or_default() += 1;
+            for cause in result.death_causes.iter() {
+                *report.death_causes.entry(cause.clone()).or_default() += 1;
+            }
+            for description in result.improvements_offered.iter() {
+                report
+                    .improvements
+                    .entry(description.clone())
+                    .or_default()
+                    .0 += 1;
+            }
+            for description in result.improvements_picked.iter() {
+                report
+                    .improvements
+                    .entry(description.clone())
+                    .or_default()
+                    .1 += 1;
+            }
+            for (ability_type, num_cast) in result.abilities_cast.iter() {
+                *report
+                    .abilities_cast
+                    .entry(String::from(ability_type.name_description().0))
+                    .or_default() += num_cast;
+            }
+        }
+        report
+    }
+
+    fn to_json(&self, bucket: usize) -> String {
+        let mut s = String::from("{\n");
+        let _ = writeln!(s, "  \"games\": {},", self.games);
+        let _ = writeln!(s, "  \"games_over\": {},", self.games_over);
+        let _ = writeln!(s, "  \"survival_turns\": {{");
+        let _ = writeln!(s, "    \"mean\": {:.3},", self.mean_turns);
+        let _ = writeln!(s, "    \"median\": {},", self.median_turns);
+        let _ = writeln!(s, "    \"min\": {},", self.min_turns);
+        let _ = writeln!(s, "    \"max\": {},", self.max_turns);
+        let _ = writeln!(s, "    \"bucket\": {},", bucket);
+        let histogram: Vec<String> = self
+            .survival_histogram
+            .iter()
+            .map(|(start, n)| format!("      {{ \"from\": {}, \"games\": {} }}", start, n))
+            .collect();
+        let _ = writeln!(s, "    \"histogram\": [\n{}\n    ]", histogram.join(",\n"));
+        let _ = writeln!(s, "  }},");
+        let _ = writeln!(
+            s,
+            "  \"death_causes\": {},",
+            json_counts(&self.death_causes)
+        );
+        let improvements: Vec<String> = self
+            .improvements
+            .iter()
+            .map(|(description, (offered, picked))| {
+                format!(
+                    "    {}: {{ \"offered\": {}, \"picked\": {}, \"pick_rate\": {:.4} }}",
+                    json_string(description),
+                    offered,
+                    picked,
+                    mean(*picked, *offered)
+                )
+            })
+            .collect();
+        let _ = writeln!(
+            s,
+            "  \"improvements\": {{\n{}\n  }},",
+            improvements.join(",\n")
+        );
+        let _ = writeln!(
+            s,
+            "  \"abilities_cast\": {},",
+            json_counts(&self.abilities_cast)
+        );
+        let _ = writeln!(s, "  \"mean_level_reached\": {:.3}", self.mean_level);
+        s.push_str("}\n");
+        s
+    }
+
+    // one row per number, in long form so it can be pivoted however it's needed
+    fn to_csv(&self) -> String {
+        let mut s = String::from("section,key,value\n");
+        let _ = writeln!(s, "games,,{}", self.games);
+        let _ = writeln!(s, "games_over,,{}", self.games_over);
+        let _ = writeln!(s, "survival_turns,mean,{:.3}", self.mean_turns);
+        let _ = writeln!(s, "survival_turns,median,{}", self.median_turns);
+        let _ = writeln!(s, "survival_turns,min,{}", self.min_turns);
+        let _ = writeln!(s, "survival_turns,max,{}", self.max_turns);
+        for (start, n) in self.survival_histogram.iter() {
+            let _ = writeln!(s, "survival_histogram,{},{}", start, n);
+        }
+        for (cause, n) in self.death_causes.iter() {
+            let _ = writeln!(s, "death_cause,{},{}", csv_field(cause), n);
+        }
+        for (description, (offered, picked)) in self.improvements.iter() {
+            let key = csv_field(description);
+            let _ = writeln!(s, "improvement_offered,{},{}", key, offered);
+            let _ = writeln!(s, "improvement_picked,{},{}", key, picked);
+            let _ = writeln!(
+                s,
+                "improvement_pick_rate,{},{:.4}",
+                key,
+                mean(*picked, *offered)
+            );
+        }
+        for (ability, n) in self.abilities_cast.iter() {
+            let _ = writeln!(s, "ability_cast,{},{}", csv_field(ability), n);
+            let _ = writeln!(
+                s,
+                "ability_cast_per_game,{},{:.4}",
+                csv_field(ability),
+                mean(*n, self.games)
+            );
+        }
+        let _ = writeln!(s, "mean_level_reached,,{:.3}", self.mean_level);
+        s
+    }
+}
+
+fn mean(total: usize, count: usize) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        total as f64 / count as f64
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_counts(counts: &BTreeMap<String, usize>) -> String {
+    let entries: Vec<String> = counts
+        .iter()
+        .map(|(key, n)| format!("{}: {}", json_string(key), n))
+        .collect();
+    format!("{{ {} }}", entries.join(", "))
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        String::from(s)
+    }
+}
+
+fn run() -> Result<(), String> {
+    let options = match parse_options(std::env::args().skip(1))? {
+        Some(options) => options,
+        None => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+    };
+    let results = play_games(&options)?;
+    let report = Report::new(&results, options.bucket);
+    let output = match options.format {
+        Format::Json => report.to_json(options.bucket),
+        Format::Csv => report.to_csv(),
+    };
+    match options.out {
+        Some(ref path) => {
+            std::fs::write(path, output).map_err(|e| format!("failed to write {}: {}", path, e))
+        }
+        None => std::io::stdout()
+            .write_all(output.as_bytes())
+            .map_err(|e| e.to_string()),
+    }
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,413 @@
+// a plain text frontend for playing the core directly, without the full GUI.
+// run with --help for the options, and type h in game for the commands
+use dungeon_raid_core::game::config::GameConfig;
+use dungeon_raid_core::game::event::GameEvent;
+use dungeon_raid_core::game::tile::{Tile, TileInfo, TilePosition, TileType};
+use dungeon_raid_core::game::turn::TurnPhase;
+use dungeon_raid_core::game::{Game, SpecialInfo, SpecialType};
+
+use std::io::{BufRead, Write};
+
+const USAGE: &str = "usage: terminal [options]
+
+options:
+  --seed S          play the run with this seed (default: random)
+  --undo N          allow undoing up to N turns (default: off)
+  --help            print this message";
+
+const COMMANDS: &str = "commands:
+  a1 b2 c3 ...      submit a path, columns are letters and rows are numbers
+  p a1 b2 c3 ...    preview a path without submitting it
+  c N               cast the ability in slot N
+  1 2 ...           pick improvements by number while choosing them
+  u / r             undo or redo the last turn, if started with --undo
+  h                 show this help
+  q                 quit";
+
+fn tile_glyph(tile: &Tile) -> char {
+    match tile.tile_type {
+        TileType::Potion => '+',
+        TileType::Shield => '#',
+        TileType::Coin => '$',
+        TileType::Sword => '/',
+        TileType::Enemy => 'e',
+        TileType::Special => match tile.tile_info {
+            TileInfo::Special(ref s) => special_glyph(s.special_type),
+            _ => '?',
+        },
+        TileType::COUNT | TileType::None => ' ',
+    }
+}
+
+fn special_glyph(special_type: SpecialType) -> char {
+    match special_type {
+        SpecialType::Boss => 'B',
+        SpecialType::Chaotic => 'C',
+        SpecialType::Precise => 'P',
+        SpecialType::Undead => 'U',
+        SpecialType::Resourceful => 'R',
+        SpecialType::Enlightener => 'E',
+        SpecialType::Kamikaze => 'K',
+        SpecialType::COUNT => '?',
+    }
+}
+
+fn column_letter(x: usize) -> char {
+    (b'a' + x as u8) as char
+}
+
+fn position_name(tile_position: &TilePosition) -> String {
+    format!(
+        "{}{}",
+        column_letter(tile_position.x as usize),
+        tile_position.y + 1
+    )
+}
+
+// "b3" is column b, row 3, i.e. TilePosition { y: 2, x: 1 }
+fn parse_position(s: &str) -> Option<TilePosition> {
+    let mut chars = s.chars();
+    let column = chars.next()?.to_ascii_lowercase();
+    if !column.is_ascii_lowercase() {
+        return None;
+    }
+    let row: isize = chars.as_str().parse().ok()?;
+    if row < 1 {
+        return None;
+    }
+    Some(TilePosition::new(row - 1, (column as u8 - b'a') as isize))
+}
+
+fn parse_path(words: &[&str]) -> Result<Vec<TilePosition>, String> {
+    words
+        .iter()
+        .map(|w| parse_position(w).ok_or_else(|| format!("\"{}\" is not a tile like b3", w)))
+        .collect()
+}
+
+fn draw_board(game: &Game) {
+    let view = game.board_view();
+    let selection = view.selection();
+    let mut header = String::from("   ");
+    for x in 0..view.width() {
+        header.push_str(&format!(" {} ", column_letter(x)));
+    }
+    println!("{}", header);
+    for (y, row) in view.rows().enumerate() {
+        let mut line = format!("{:>2} ", y + 1);
+        for (x, tile) in row.iter().enumerate() {
+            let selected = selection.contains(&TilePosition::new(y as isize, x as isize));
+            let (open, close) = if selected { ('[', ']') } else { (' ', ' ') };
+            line.push_str(&format!("{}{}{}", open, tile_glyph(tile), close));
+        }
+        println!("{}", line);
+    }
+    println!("   + potion  # shield  $ coin  / sword  e enemy  capitals are specials");
+}
+
+fn percent(cents: usize, cents_needed: usize) -> usize {
+    cents * 100 / std::cmp::max(cents_needed, 1)
+}
+
+fn draw_player(game: &Game) {
+    let player = game.player();
+    let being = &player.being;
+    println!(
+        "HP {}/{}  shields {}/{}  damage {} + {} per sword  turn {}  incoming damage {}",
+        being.hit_points,
+        being.max_hit_points,
+        being.shields,
+        being.max_shields,
+        being.base_output_damage,
+        being.weapon_output_damage,
+        game.run_summary().turns_survived,
+        game.incoming_damage()
+    );
+    println!(
+        "toward the next purchase {}%  shield upgrade {}%  level up {}%",
+        percent(player.coin_cents, player.coin_cents_per_purchase),
+        percent(
+            player.excess_shield_cents,
+            player.excess_shield_cents_per_upgrade
+        ),
+        percent(
+            player.experience_point_cents,
+            player.experience_point_cents_per_level_up
+        )
+    );
+    for (slot, ability_opt) in player.abilities.iter().enumerate() {
+        match ability_opt {
+            Some(a) => {
+                let (name, description) = a.ability_type.name_description();
+                let state = if a.running_cooldown == 0 {
+                    String::from("ready")
+                } else {
+                    format!("{}/{} turns", a.running_cooldown, a.cooldown)
+                };
+                println!(
+                    "  ability {}: {} lv{} ({}) - {}",
+                    slot + 1,
+                    name,
+                    a.current_level,
+                    state,
+                    description
+                );
+            }
+            None => println!("  ability {}: empty", slot + 1),
+        }
+    }
+}
+
+fn draw_specials(game: &Game) {
+    let ruleset = game.config().ruleset();
+    for (tile_position, tile, _) in game.specials() {
+        if let TileInfo::Special(s) = tile.tile_info {
+            let (name, description) = s.special_type.name_description(ruleset);
+            let countdown = match s.special_info {
+                SpecialInfo::Kamikaze(turns) => format!(", explodes in {} turns", turns),
+                SpecialInfo::Enlightener(turns) => format!(", enlightens in {} turns", turns),
+                SpecialInfo::Undead(false) => String::from(", will reanimate"),
+                _ => String::new(),
+            };
+            println!(
+                "  {} {} at {}: HP {}/{} shields {} attack {}{} - {}",
+                special_glyph(s.special_type),
+                name,
+                position_name(&tile_position),
+                s.being.hit_points,
+                s.being.max_hit_points,
+                s.being.shields,
+                s.output_damage(1, 0),
+                countdown,
+                description
+            );
+        }
+    }
+}
+
+fn draw_improvements(game: &Game) {
+    let set = match game.improvement_choice_set() {
+        Some(set) => set,
+        None => return,
+    };
+    println!("{} - pick {}:", set.header, set.num_to_choose);
+    for (index, display) in set.displays.iter().enumerate() {
+        println!("  {}: {}", index + 1, display.description);
+    }
+}
+
+fn draw(game: &Game) {
+    println!();
+    draw_board(game);
+    draw_player(game);
+    draw_specials(game);
+    draw_improvements(game);
+}
+
+fn describe_event(event: &GameEvent) -> Option<String> {
+    Some(match event {
+        GameEvent::TileHit {
+            position,
+            tile_type: TileType::Enemy | TileType::Special,
+            damage,
+            destroyed,
+            ..
+        } => format!(
+            "hit {} for {}{}",
+            position_name(position),
+            damage,
+            if *destroyed { ", killed it" } else { "" }
+        ),
+        GameEvent::TileHit { .. } => return None,
+        GameEvent::TilesCollected {
+            hit_points_restored,
+            shields,
+            coins,
+            experience_points,
+            ..
+        } => format!(
+            "collected {} HP, {} shields, {} coins, {} experience",
+            hit_points_restored, shields, coins, experience_points
+        ),
+        GameEvent::PlayerDamaged {
+            damage,
+            shields_absorbed,
+            hit_points_lost,
+        } => format!(
+            "took {} damage, {} absorbed by shields, {} HP lost",
+            damage, shields_absorbed, hit_points_lost
+        ),
+        GameEvent::BluntingApplied { blunting } => format!("blunted attackers by {}", blunting),
+        GameEvent::ImprovementQueued(improvement_type) => {
+            format!("earned an improvement: {:?}", improvement_type)
+        }
+        GameEvent::AbilityCast { ability_type, .. } => {
+            format!("cast {}", ability_type.name_description().0)
+        }
+        GameEvent::SpecialSpawned {
+            position,
+            special_type,
+            ..
+        } => format!(
+            "a {:?} appeared at {}",
+            special_type,
+            position_name(position)
+        ),
+        GameEvent::UndeadReanimated { position, .. } => {
+            format!("the undead at {} reanimated", position_name(position))
+        }
+        GameEvent::EnlightenerConverted { position, .. } => {
+            format!("the enemy at {} was enlightened", position_name(position))
+        }
+        GameEvent::KamikazeTicked { .. } => return None,
+        GameEvent::KamikazeExploded { position, .. } => {
+            format!("the kamikaze at {} exploded", position_name(position))
+        }
+        GameEvent::PlayerDied(cause) => format!("you died: {:?}", cause),
+        GameEvent::BoardReshuffled => String::from("no moves left, the board was reshuffled"),
+        GameEvent::TurnUndone => String::from("undid the last turn"),
+        GameEvent::TurnRedone => String::from("redid the turn"),
+    })
+}
+
+fn print_events(game: &mut Game) {
+    for event in game.take_events() {
+        if let Some(description) = describe_event(&event) {
+            println!("* {}", description);
+        }
+    }
+    // nothing is animated, so movements are just dropped
+    game.take_tile_movements();
+}
+
+// returns false once the player wants to quit
+fn handle_line(game: &mut Game, line: &str) -> bool {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let result = match words.as_slice() {
+        [] => Ok(()),
+        ["q"] | ["quit"] => return false,
+        ["h"] | ["help"] => {
+            println!("{}", COMMANDS);
+            Ok(())
+        }
+        ["u"] | ["undo"] => game.undo().map_err(|e| e.to_string()),
+        ["r"] | ["redo"] => game.redo().map_err(|e| e.to_string()),
+        ["c", slot] | ["cast", slot] => match slot.parse::<usize>() {
+            Ok(slot) if slot > 0 => game.cast_ability(slot - 1).map_err(|e| e.to_string()),
+            _ => Err(format!("\"{}\" is not an ability slot", slot)),
+        },
+        ["p", path @ ..] => parse_path(path).and_then(|path| {
+            let preview = game.preview_path(&path).map_err(|e| e.to_string())?;
+            let damage: usize = preview.tiles.iter().map(|t| t.damage).sum();
+            let killed = preview.tiles.iter().filter(|t| t.destroyed).count();
+            println!(
+                "would deal {} damage, destroy {} tiles and collect {} HP, {} shields, {} coins, {} experience",
+                damage,
+                killed,
+                preview.hit_points_restored,
+                preview.shields,
+                preview.coins,
+                preview.experience_points
+            );
+            Ok(())
+        }),
+        _ if game.phase() == TurnPhase::ChoosingImprovement => {
+            let indeces: Result<Vec<usize>, _> = words
+                .iter()
+                .map(|w| match w.parse::<usize>() {
+                    Ok(n) if n > 0 => Ok(n - 1),
+                    _ => Err(format!("\"{}\" is not an improvement number", w)),
+                })
+                .collect();
+            indeces.and_then(|indeces| {
+                game.choose_improvements(&indeces)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            })
+        }
+        _ => parse_path(&words).and_then(|path| {
+            game.submit_path(&path)
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        }),
+    };
+    // help and previews don't change anything, so there's no need to redraw
+    let changes_game = !matches!(words.first(), None | Some(&"h" | &"help" | &"p"));
+    match result {
+        Ok(()) => {
+            print_events(game);
+            if changes_game {
+                draw(game);
+            }
+        }
+        Err(e) => println!("! {}", e),
+    }
+    true
+}
+
+fn parse_config(mut args: impl Iterator<Item = String>) -> Result<Option<GameConfig>, String> {
+    let mut builder = GameConfig::builder();
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--seed" => {
+                let seed = args.next().ok_or("--seed needs a value")?;
+                let seed = seed
+                    .parse()
+                    .map_err(|_| format!("--seed expects a number, got \"{}\"", seed))?;
+                builder = builder.seed(seed);
+            }
+            "--undo" => {
+                let undo_limit = args.next().ok_or("--undo needs a value")?;
+                let undo_limit = undo_limit
+                    .parse()
+                    .map_err(|_| format!("--undo expects a number, got \"{}\"", undo_limit))?;
+                builder = builder.undo_limit(undo_limit);
+            }
+            "--help" | "-h" => return Ok(None),
+            _ => return Err(format!("unknown option \"{}\"", flag)),
+        }
+    }
+    builder.build().map(Some).map_err(|e| e.to_string())
+}
+
+fn main() {
+    let config = match parse_config(std::env::args().skip(1)) {
+        Ok(Some(config)) => config,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            std::process::exit(1);
+        }
+    };
+    let mut game = Game::new(config);
+    println!("seed {}, type h for help", game.seed());
+    print_events(&mut game);
+    draw(&game);
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        if game.is_game_over() {
+            let summary = game.run_summary();
+            println!(
+                "game over after {} turns: {} enemies killed, {} level ups ({}q to quit)",
+                summary.turns_survived,
+                summary.enemies_killed,
+                summary.level_ups,
+                if game.can_undo() { "u to undo, " } else { "" }
+            );
+        }
+        print!("> ");
+        let _ = std::io::stdout().flush();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => return,
+        };
+        if !handle_line(&mut game, &line) {
+            return;
+        }
+    }
+}
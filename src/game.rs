@@ -2,37 +2,84 @@ mod board;
 use board::Board;
 
 pub mod tile;
-use tile::{Tile, TilePosition, TileType};
+use tile::{Tile, TileId, TileInfo, TilePosition, TileType};
 
 mod collection_multipliers;
 use collection_multipliers::CollectionMultipliers;
 
 mod randomizer;
+use randomizer::GameRng;
 
 mod being;
-use being::{Being, BeingType};
+pub use being::{Being, BeingType};
 
 mod player;
 use player::{Player, PlayerIsDead};
 
 mod special;
-use special::{Special, SpecialGenerator, SpecialIdentifier};
+use special::SpecialGenerator;
+pub use special::{Special, SpecialIdentifier, SpecialInfo, SpecialType};
 
 mod stat_modifiers;
 
 mod abilities;
-use abilities::AbilityType;
+pub use abilities::AbilityType;
 
 mod coin_purchase;
 mod experience_point_level_up;
 use experience_point_level_up::ExperiencePointLevelUpInfo;
 mod shield_upgrade;
 
+#[cfg(feature = "serde")]
+pub mod save;
+
+pub mod replay;
+use replay::GameAction;
+
+pub mod turn;
+use turn::{TurnOutcome, TurnPhase, WrongPhase};
+
+pub mod error;
+use error::{AbilityError, GameError, ImprovementError};
+
+pub mod config;
+use config::GameConfig;
+
+pub mod ruleset;
+
+pub mod preview;
+use preview::{SelectionPreview, TilePreview};
+
+pub mod board_view;
+use board_view::BoardView;
+
+pub mod legal_paths;
+use legal_paths::{LegalPaths, PathLimits};
+
+pub mod movement;
+use movement::TileMovement;
+
+mod undo;
+use undo::UndoHistory;
+
+pub mod state_hash;
+
+pub mod agent;
+
+pub mod runner;
+
+pub mod event;
+use event::GameEvent;
+
+pub mod run_summary;
+use run_summary::{CauseOfDeath, ChosenImprovement, RunStats, RunSummary};
+
 pub mod improvement_choices;
 use improvement_choices::{
     ImprovementChoiceSet, ImprovementChoiceSetGenerator, ImprovementInfo, ImprovementType,
 };
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     turns_passed: usize,
     most_recent_special_kill_turn: usize,
@@ -45,6 +92,17 @@ pub struct Game {
     improvement_choice_set: Option<ImprovementChoiceSet>,
     improvement_queue: Vec<ImprovementType>,
     collection_multipliers: CollectionMultipliers,
+    config: GameConfig,
+    rng: GameRng,
+    action_log: Vec<GameAction>,
+    phase: TurnPhase,
+    run_stats: RunStats,
+    // snapshots for undo, too big to be worth saving
+    #[cfg_attr(feature = "serde", serde(skip))]
+    history: UndoHistory,
+    // waiting to be taken by the caller, not part of the game state
+    #[cfg_attr(feature = "serde", serde(skip))]
+    events: Vec<GameEvent>,
 }
 
 pub const DEFAULT_BOARD_WIDTH: usize = 6;
@@ -52,86 +110,433 @@ pub const DEFAULT_BOARD_HEIGHT: usize = 6;
 
 pub const ABILITY_SLOTS: usize = 4;
 
-const INITIAL_MIN_TURNS_BETWEEN_SPECIALS: usize = 2;
+const ENEMY_EXPERIENCE_POINTS: usize = 1;
+const SPECIAL_EXPERIENCE_POINTS: usize = 15;
+
+const MAX_DEAD_BOARD_SCRAMBLES: usize = 10;
+const MAX_DEAD_BOARD_REROLLS: usize = 100;
 
 impl Default for Game {
     fn default() -> Game {
-        let enemy = Being::new(BeingType::Enemy, 1, 1);
-        let mut special_generator = SpecialGenerator::default();
+        Game::new(GameConfig::default())
+    }
+}
+
+// the undo history and anything waiting to be taken by the caller stay behind, which
+// keeps clones cheap enough for search and rollouts
+impl Clone for Game {
+    fn clone(&self) -> Game {
+        let mut game = self.clone_without_action_log();
+        game.action_log = self.action_log.clone();
+        game
+    }
+}
+
+impl Game {
+    // undo snapshots leave the action log out, since it's only ever appended to and
+    // the live game's log already starts with theirs
+    fn clone_without_action_log(&self) -> Game {
+        let mut board = self.board.clone();
+        board.take_movements();
         Game {
+            turns_passed: self.turns_passed,
+            most_recent_special_kill_turn: self.most_recent_special_kill_turn,
+            min_turns_between_specials: self.min_turns_between_specials,
+            board,
+            player: self.player.clone(),
+            enemy: self.enemy,
+            special_generator: self.special_generator.clone(),
+            improvement_choice_set_generator: self.improvement_choice_set_generator.clone(),
+            improvement_choice_set: self.improvement_choice_set.clone(),
+            improvement_queue: self.improvement_queue.clone(),
+            collection_multipliers: self.collection_multipliers.clone(),
+            config: self.config.clone(),
+            rng: self.rng.clone(),
+            action_log: vec![],
+            phase: self.phase,
+            run_stats: self.run_stats.clone(),
+            history: UndoHistory::default(),
+            events: vec![],
+        }
+    }
+
+    pub fn with_seed(seed: u64) -> Game {
+        Game::new(
+            GameConfig::builder()
+                .seed(seed)
+                .build()
+                .expect("the default config with a seed should always be valid"),
+        )
+    }
+
+    // a config without a seed gets a random one, which is then stored back
+    // into the config so the run can be reproduced
+    pub fn new(mut config: GameConfig) -> Game {
+        let seed = config.seed().unwrap_or_else(rand::random::<u64>);
+        config.set_seed(seed);
+        let mut rng = randomizer::rng_from_seed(seed);
+        let enemy = Being::new(BeingType::Enemy, config.enemy_start_stats(), 1, 1);
+        let mut special_generator =
+            SpecialGenerator::new(*config.special_start_stats(), config.shared_ruleset());
+        let mut game = Game {
             turns_passed: 0,
             most_recent_special_kill_turn: 0,
-            min_turns_between_specials: INITIAL_MIN_TURNS_BETWEEN_SPECIALS,
-            board: Board::new(
-                DEFAULT_BOARD_WIDTH,
-                DEFAULT_BOARD_HEIGHT,
-                &enemy,
-                &mut special_generator,
+            min_turns_between_specials: config.initial_min_turns_between_specials(),
+            board: Board::new(&config, &enemy, &mut special_generator, &mut rng),
+            player: Player::new(
+                config.player_start_stats(),
+                config.ability_slots(),
+                config.ruleset(),
             ),
-            player: Player::default(),
             enemy,
             special_generator,
-            improvement_choice_set_generator: ImprovementChoiceSetGenerator::default(),
+            improvement_choice_set_generator: ImprovementChoiceSetGenerator::new(
+                config.ability_slots(),
+                config.ruleset(),
+            ),
             improvement_choice_set: None,
             improvement_queue: vec![],
             collection_multipliers: CollectionMultipliers::default(),
+            config,
+            rng,
+            action_log: vec![],
+            phase: TurnPhase::AwaitingSelection,
+            run_stats: RunStats::default(),
+            history: UndoHistory::default(),
+            events: vec![],
+        };
+        game.reshuffle_if_dead();
+        game
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.config
+            .seed()
+            .expect("Game::new always stores the seed in its config")
+    }
+
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    pub fn action_log(&self) -> &[GameAction] {
+        &self.action_log
+    }
+
+    // every action is logged through here. acting after an undo starts a new line of
+    // play, so the undone turns can't be redone anymore
+    fn log_action(&mut self, action: GameAction) {
+        self.clear_redo();
+        self.action_log.push(action);
+    }
+
+    // mostly useful for driving a game from a recorded or remote source
+    pub fn apply_action(&mut self, action: &GameAction) -> Result<(), GameError> {
+        match action {
+            GameAction::SelectTile(tile_position) => self.select_tile(tile_position),
+            GameAction::BacktrackSelection => self.backtrack_selection().map(|_| ()),
+            GameAction::CancelSelection => self.cancel_selection(),
+            GameAction::SubmitTurn => self.submit_turn().map(|_| ()),
+            GameAction::CastAbility(index) => self.cast_ability(*index),
+            GameAction::ChooseImprovements(indeces) => {
+                self.choose_improvements(indeces).map(|_| ())
+            }
+        }
+    }
+
+    pub fn phase(&self) -> TurnPhase {
+        self.phase
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.phase == TurnPhase::GameOver
+    }
+
+    // can be asked for mid-run, in which case cause_of_death is None
+    pub fn run_summary(&self) -> RunSummary {
+        self.run_stats
+            .summarize(self.turns_passed, self.player.being)
+    }
+
+    fn expect_phase(&self, expected: TurnPhase) -> Result<(), WrongPhase> {
+        if self.phase == expected {
+            Ok(())
+        } else {
+            Err(WrongPhase {
+                expected,
+                actual: self.phase,
+            })
+        }
+    }
+
+    // drops the current selection and, if it hit, runs the rest of the turn until the
+    // player has to choose an improvement or the turn is over
+    pub fn submit_turn(&mut self) -> Result<TurnOutcome, GameError> {
+        self.expect_phase(TurnPhase::AwaitingSelection)?;
+        let snapshot = self.snapshot_for_undo();
+        self.log_action(GameAction::SubmitTurn);
+        let hit = self.drop_selection();
+        let mut board_reshuffled = false;
+        if hit {
+            if let Some(snapshot) = snapshot {
+                self.push_undo_snapshot(snapshot);
+            }
+            self.phase = TurnPhase::ChoosingImprovement;
+            board_reshuffled = self.run_phases();
+        }
+        Ok(TurnOutcome {
+            hit,
+            phase: self.phase,
+            board_reshuffled,
+        })
+    }
+
+    // returns whether the board had no legal paths and was reshuffled
+    fn run_phases(&mut self) -> bool {
+        loop {
+            match self.phase {
+                TurnPhase::ChoosingImprovement => {
+                    if self.improvement_choice_set.is_some() {
+                        return false;
+                    }
+                    self.phase = TurnPhase::EnemyPhase;
+                }
+                TurnPhase::EnemyPhase => {
+                    let attacking_specials: Vec<SpecialType> = self
+                        .specials()
+                        .iter()
+                        .filter_map(|(_, tile, _)| match tile.tile_info {
+                            TileInfo::Special(ref s) if s.output_damage(1, 0) > 0 => {
+                                Some(s.special_type)
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    self.phase = if self.apply_incoming_damage() {
+                        let cause = CauseOfDeath::EnemyAttack { attacking_specials };
+                        self.run_stats.cause_of_death = Some(cause.clone());
+                        self.events.push(GameEvent::PlayerDied(cause));
+                        TurnPhase::GameOver
+                    } else {
+                        TurnPhase::Refill
+                    };
+                }
+                TurnPhase::Refill => {
+                    let first_new_id = self.special_generator.unused_id();
+                    self.apply_gravity_and_randomize_new_tiles();
+                    self.push_special_spawns(first_new_id);
+                    self.phase = TurnPhase::SpecialsPhase;
+                }
+                TurnPhase::SpecialsPhase => {
+                    self.run_end_of_turn_on_specials();
+                    if let Some(ref cause) = self.run_stats.cause_of_death {
+                        self.events.push(GameEvent::PlayerDied(cause.clone()));
+                        self.phase = TurnPhase::GameOver;
+                    } else {
+                        // checked after specials rather than right after the refill
+                        // since specials can move and replace tiles too
+                        self.phase = TurnPhase::AwaitingSelection;
+                        return self.reshuffle_if_dead();
+                    }
+                }
+                TurnPhase::AwaitingSelection | TurnPhase::GameOver => return false,
+            }
         }
     }
-}
 
-impl Game {
     pub fn player(&self) -> &Player {
         &self.player
     }
 
     pub fn improvement_choice_set(&self) -> Option<&ImprovementChoiceSet> {
-        match self.improvement_choice_set {
-            Some(ref ics) => Some(ics),
-            None => None,
-        }
+        self.improvement_choice_set.as_ref()
     }
 
     pub fn incoming_damage(&self) -> usize {
         self.board.incoming_damage()
     }
 
+    // 100 at the start of a run, higher means tougher enemies and specials
+    pub fn difficulty_percent(&self) -> usize {
+        self.config
+            .ruleset()
+            .difficulty_curve
+            .percent(self.turns_passed)
+    }
+
+    // the stats a newly created enemy tile gets right now
+    pub fn current_enemy_template(&self) -> &Being {
+        &self.enemy
+    }
+
     pub fn specials(&self) -> Vec<(TilePosition, Tile, usize)> {
-        self.board.specials(&vec![])
+        self.board.specials(&[])
     }
 
-    pub fn apply_incoming_damage(&mut self) -> PlayerIsDead {
+    fn apply_incoming_damage(&mut self) -> PlayerIsDead {
         let player_has_shields = self.player.being.shields > 0;
-        let player_is_dead = self.player.take_damage(self.board.incoming_damage());
+        let player_is_dead = self.damage_player(self.board.incoming_damage());
         if player_has_shields {
-            self.board
-                .apply_blunting(self.player.stat_modifiers.blunting);
+            let blunting = self.player.stat_modifiers.blunting;
+            self.board.apply_blunting(blunting);
+            if blunting > 0 {
+                self.events.push(GameEvent::BluntingApplied { blunting });
+            }
         }
 
         player_is_dead
     }
 
-    pub fn select_tile(&mut self, tile_position: &TilePosition) -> bool {
-        self.board.select_tile(tile_position)
+    fn damage_player(&mut self, damage: usize) -> PlayerIsDead {
+        let before = self.player.being;
+        let player_is_dead = self.player.take_damage(damage);
+        if damage > 0 {
+            self.events.push(GameEvent::PlayerDamaged {
+                damage,
+                shields_absorbed: before.shields - self.player.being.shields,
+                hit_points_lost: before.hit_points - self.player.being.hit_points,
+            });
+        }
+        player_is_dead
+    }
+
+    pub fn select_tile(&mut self, tile_position: &TilePosition) -> Result<(), GameError> {
+        self.expect_phase(TurnPhase::AwaitingSelection)?;
+        self.board.select_tile(tile_position)?;
+        self.log_action(GameAction::SelectTile(*tile_position));
+        Ok(())
+    }
+
+    // like select_tile, but a tile further away in a straight or diagonal line also
+    // selects every tile in between, as long as all of them can be selected
+    pub fn select_tile_filling_gaps(
+        &mut self,
+        tile_position: &TilePosition,
+    ) -> Result<(), GameError> {
+        self.expect_phase(TurnPhase::AwaitingSelection)?;
+        for p in self.board.gap_fill(tile_position)?.iter() {
+            self.select_tile(p)
+                .expect("gap_fill should have caught anything select_tile rejects");
+        }
+        Ok(())
+    }
+
+    // removes the last selected tile, returning where it was
+    pub fn backtrack_selection(&mut self) -> Result<TilePosition, GameError> {
+        self.expect_phase(TurnPhase::AwaitingSelection)?;
+        let removed = self.board.backtrack_selection()?;
+        self.log_action(GameAction::BacktrackSelection);
+        Ok(removed)
+    }
+
+    // clears the selection without submitting it, does nothing if nothing is selected
+    pub fn cancel_selection(&mut self) -> Result<(), GameError> {
+        self.expect_phase(TurnPhase::AwaitingSelection)?;
+        if self.board.selection_start.is_some() {
+            self.board.cancel_selection();
+            self.log_action(GameAction::CancelSelection);
+        }
+        Ok(())
+    }
+
+    // selects the whole path and submits it, replacing any current selection.
+    // nothing changes unless the whole path is valid and long enough to hit
+    pub fn submit_path(&mut self, path: &[TilePosition]) -> Result<TurnOutcome, GameError> {
+        self.expect_phase(TurnPhase::AwaitingSelection)?;
+        self.board.validate_path(path)?;
+        self.cancel_selection()?;
+        for tile_position in path.iter() {
+            self.select_tile(tile_position)
+                .expect("validate_path should have caught anything select_tile rejects");
+        }
+        self.submit_turn()
+    }
+
+    // in the order the tiles were selected, empty if nothing is selected
+    pub fn selection_path(&self) -> Vec<TilePosition> {
+        self.board.selection()
+    }
+
+    // a board with no legal paths would leave the player stuck, so it's scrambled
+    // for free. if the tiles on it can't make a path in any order, the non-special
+    // tiles are rerolled instead. if even that never finds a path, which takes a
+    // degenerate config, the run ends. returns whether the board was changed
+    fn reshuffle_if_dead(&mut self) -> bool {
+        if self.board.has_legal_path() {
+            return false;
+        }
+        self.events.push(GameEvent::BoardReshuffled);
+        for _ in 0..MAX_DEAD_BOARD_SCRAMBLES {
+            self.board.scramble(&mut self.rng);
+            if self.board.has_legal_path() {
+                return true;
+            }
+        }
+        // with a sane config this always finds a path long before running out
+        let first_new_id = self.special_generator.unused_id();
+        for _ in 0..MAX_DEAD_BOARD_REROLLS {
+            self.board
+                .reroll(&self.enemy, &mut self.special_generator, &mut self.rng);
+            if self.board.has_legal_path() {
+                break;
+            }
+        }
+        self.push_special_spawns(first_new_id);
+        if !self.board.has_legal_path() {
+            let cause = CauseOfDeath::NoLegalPath;
+            self.run_stats.cause_of_death = Some(cause.clone());
+            self.events.push(GameEvent::PlayerDied(cause));
+            self.phase = TurnPhase::GameOver;
+        }
+        true
+    }
+
+    // new enemies and specials are created from the difficulty at the current turn,
+    // the ones already on the board keep the stats they were created with
+    fn update_difficulty(&mut self) {
+        let difficulty_percent = self.difficulty_percent();
+        self.enemy = Being::new(
+            BeingType::Enemy,
+            self.config.enemy_start_stats(),
+            difficulty_percent,
+            100,
+        );
+        self.special_generator
+            .set_difficulty_percent(difficulty_percent);
+    }
+
+    fn queue_improvement(&mut self, improvement_type: ImprovementType) {
+        self.improvement_queue.push(improvement_type);
+        self.events
+            .push(GameEvent::ImprovementQueued(improvement_type));
     }
 
     fn step_improvement_queue(&mut self) {
         match self.improvement_queue.pop() {
             Some(imp_type) => {
-                self.improvement_choice_set =
-                    Some(self.improvement_choice_set_generator.get(imp_type))
+                self.improvement_choice_set = Some(
+                    self.improvement_choice_set_generator
+                        .get(imp_type, &mut self.rng),
+                )
             }
             None => self.improvement_choice_set = None,
         }
     }
 
-    pub fn run_end_of_turn_on_specials(&mut self) {
+    fn run_end_of_turn_on_specials(&mut self) {
         let num_tiles = self.board.num_tiles();
         let mut special_ids_run: Vec<SpecialIdentifier> = Vec::with_capacity(num_tiles);
         'outer: for _ in 0..num_tiles {
             let specials = self.board.specials(&special_ids_run);
             for (tile_pos, _tile, id) in specials.iter() {
                 special_ids_run.push(*id);
+                let first_new_id = self.special_generator.unused_id();
                 let modifies_board = Special::end_of_turn(self, tile_pos);
+                self.push_special_spawns(first_new_id);
+                // nothing happens after the player dies, so PlayerDied is the last event
+                if self.run_stats.cause_of_death.is_some() {
+                    return;
+                }
                 if modifies_board {
                     continue 'outer;
                 }
@@ -141,14 +546,34 @@ impl Game {
         unreachable!("insane that we'd ever get here");
     }
 
-    pub fn drop_selection(&mut self) -> bool {
-        let (hit, vec) = self.board.drop_selection(
-            &self.player,
-            self.collection_multipliers.weapon_collection_multiplier,
-        );
-        let (mut potions, mut shields, mut coins, mut experience_points, mut special_killed) =
-            (0, 0, 0, 0, false);
-        for tile in vec.iter() {
+    // specials are given increasing ids, so any with an id from first_new_id on are new
+    fn push_special_spawns(&mut self, first_new_id: SpecialIdentifier) {
+        for (position, tile, id) in self.board.specials(&[]) {
+            if id < first_new_id {
+                continue;
+            }
+            if let TileInfo::Special(ref s) = tile.tile_info {
+                self.events.push(GameEvent::SpecialSpawned {
+                    position,
+                    id,
+                    special_type: s.special_type,
+                });
+            }
+        }
+    }
+
+    // every event since the last call, in the order they happened
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    // potions, shields, coins and experience points collected from destroyed tiles
+    fn tally_collection<'a>(
+        &self,
+        destroyed_tiles: impl Iterator<Item = &'a Tile>,
+    ) -> (usize, usize, usize, usize) {
+        let (mut potions, mut shields, mut coins, mut experience_points) = (0, 0, 0, 0);
+        for tile in destroyed_tiles {
             match tile.tile_type {
                 TileType::Potion => potions += 1,
                 TileType::Shield => {
@@ -156,63 +581,163 @@ impl Game {
                 }
                 TileType::Coin => coins += self.collection_multipliers.coin_collection_multiplier,
                 TileType::Sword => {}
-                TileType::Enemy => experience_points += 1,
-                TileType::Special => {
-                    experience_points += 15;
-                    special_killed = true;
-                }
+                TileType::Enemy => experience_points += ENEMY_EXPERIENCE_POINTS,
+                TileType::Special => experience_points += SPECIAL_EXPERIENCE_POINTS,
                 TileType::COUNT | TileType::None => {
                     unreachable!("drop_selection went over invalid TileType")
                 }
             };
         }
+        (potions, shields, coins, experience_points)
+    }
+
+    // mirrors what submit_turn would do with the current selection without changing anything
+    pub fn preview_selection(&self) -> SelectionPreview {
+        self.preview_selection_on(&self.board)
+    }
+
+    // what submit_path would do with this path, leaving the current selection alone
+    pub fn preview_path(&self, path: &[TilePosition]) -> Result<SelectionPreview, GameError> {
+        self.board.validate_path(path)?;
+        let mut board = self.board.clone();
+        board.cancel_selection();
+        for tile_position in path.iter() {
+            board
+                .select_tile(tile_position)
+                .expect("validate_path should have caught anything select_tile rejects");
+        }
+        Ok(self.preview_selection_on(&board))
+    }
+
+    fn preview_selection_on(&self, board: &Board) -> SelectionPreview {
+        let (hit, selection_hits) = board.hit_selection(
+            &self.player,
+            self.collection_multipliers.weapon_collection_multiplier,
+        );
+        let tiles = selection_hits.iter().map(TilePreview::from).collect();
+        if !hit {
+            return SelectionPreview {
+                tiles,
+                ..SelectionPreview::default()
+            };
+        }
+        let (potions, shields, coins, experience_points) = self.tally_collection(
+            selection_hits
+                .iter()
+                .filter(|sh| sh.destroyed)
+                .map(|sh| &sh.tile),
+        );
+        SelectionPreview {
+            hit,
+            tiles,
+            potions,
+            hit_points_restored: self.player.hit_points_restored_by(potions),
+            shields,
+            coins,
+            experience_points,
+            shield_upgrades: self.player.shield_upgrades_from(shields),
+            coin_purchases: self.player.coin_purchases_from(coins),
+            level_ups: self.player.level_ups_from(experience_points),
+        }
+    }
+
+    fn drop_selection(&mut self) -> bool {
+        let (hit, selection_hits) = self.board.drop_selection(
+            &self.player,
+            self.collection_multipliers.weapon_collection_multiplier,
+        );
+        let vec: Vec<Tile> = selection_hits
+            .iter()
+            .filter(|sh| sh.destroyed)
+            .map(|sh| sh.tile)
+            .collect();
+        let (potions, shields, coins, experience_points) = self.tally_collection(vec.iter());
+        let mut special_killed = false;
+        for tile in vec.iter() {
+            match tile.tile_type {
+                TileType::Enemy => self.run_stats.enemies_killed += 1,
+                TileType::Special => {
+                    special_killed = true;
+                    if let TileInfo::Special(ref s) = tile.tile_info {
+                        self.run_stats.special_killed(s.special_type);
+                    }
+                }
+                _ => {}
+            };
+        }
         self.collection_multipliers = CollectionMultipliers::default();
 
         if hit {
+            for sh in selection_hits.iter() {
+                self.events.push(GameEvent::TileHit {
+                    position: sh.position,
+                    tile_id: sh.tile.id,
+                    tile_type: sh.tile.tile_type,
+                    damage: sh.damage,
+                    destroyed: sh.destroyed,
+                });
+                if let (true, TileInfo::Special(ref s)) = (sh.reanimated, sh.tile.tile_info) {
+                    self.events.push(GameEvent::UndeadReanimated {
+                        position: sh.position,
+                        id: s.id,
+                    });
+                }
+            }
+            self.events.push(GameEvent::TilesCollected {
+                potions,
+                hit_points_restored: self.player.hit_points_restored_by(potions),
+                shields,
+                coins,
+                experience_points,
+            });
             // collection
+            self.run_stats.potions_collected += potions;
+            self.run_stats.shields_collected += shields;
+            self.run_stats.coins_collected += coins;
             if potions > 0 {
                 self.player.add_hit_points(potions);
             }
             if shields > 0 {
-                let num_upgrades = self.player.add_shields(shields);
+                let num_upgrades = self
+                    .player
+                    .add_shields(shields, &self.config.ruleset().excess_shield_cents);
                 for _ in 0..num_upgrades {
-                    self.improvement_queue.push(ImprovementType::Shields);
+                    self.queue_improvement(ImprovementType::Shields);
                 }
             }
             if coins > 0 {
-                let num_purchases = self.player.add_coins(coins);
+                let num_purchases = self
+                    .player
+                    .add_coins(coins, &self.config.ruleset().coin_cents);
                 for _ in 0..num_purchases {
-                    self.improvement_queue.push(ImprovementType::Coins);
+                    self.queue_improvement(ImprovementType::Coins);
                 }
             }
             if experience_points > 0 {
-                let num_level_ups = self.player.add_experience_points(experience_points);
+                let num_level_ups = self.player.add_experience_points(
+                    experience_points,
+                    &self.config.ruleset().experience_point_cents,
+                );
+                self.run_stats.level_ups += num_level_ups;
                 for _ in 0..num_level_ups {
-                    self.improvement_queue
-                        .push(ImprovementType::ExperiencePoints);
+                    self.queue_improvement(ImprovementType::ExperiencePoints);
                 }
             }
             // cooldowns down by 1
-            for ability_opt in self.player.abilities.iter_mut() {
-                match ability_opt {
-                    Some(ref mut a) => {
-                        if a.running_cooldown > 0 {
-                            a.running_cooldown -= 1
-                        }
-                    }
-                    None => {}
-                };
+            for a in self.player.abilities.iter_mut().flatten() {
+                if a.running_cooldown > 0 {
+                    a.running_cooldown -= 1
+                }
             }
             // number of turns passed up by 1
             self.turns_passed += 1;
+            self.update_difficulty();
             // update min_turns_between_specials
             let mtbs_modifier = self.turns_passed / 25;
-            self.min_turns_between_specials = if INITIAL_MIN_TURNS_BETWEEN_SPECIALS > mtbs_modifier
-            {
-                INITIAL_MIN_TURNS_BETWEEN_SPECIALS - mtbs_modifier
-            } else {
-                0
-            };
+            self.min_turns_between_specials = self
+                .config
+                .initial_min_turns_between_specials()
+                .saturating_sub(mtbs_modifier);
             // update most_recent_special_kill_turn
             if special_killed {
                 self.most_recent_special_kill_turn = self.turns_passed;
@@ -231,48 +756,105 @@ impl Game {
         hit
     }
 
-    pub fn choose_improvements(&mut self, indeces: &Vec<usize>) {
-        match self.improvement_choice_set {
-            Some(ref set) => {
-                match set.info {
-                    ImprovementInfo::ShieldUpgradeInfo(ref vec_shield_upgrade) => {
-                        for given_idx in indeces.iter() {
-                            self.player.apply_upgrade(&vec_shield_upgrade[*given_idx]);
-                        }
+    fn validate_improvement_choice(&self, indeces: &[usize]) -> Result<(), ImprovementError> {
+        let set = match self.improvement_choice_set {
+            Some(ref set) => set,
+            None => return Ok(()),
+        };
+        let num_choices = set.displays.len();
+        let expected = std::cmp::min(set.num_to_choose, num_choices);
+        if indeces.len() != expected {
+            return Err(ImprovementError::WrongCount {
+                expected,
+                given: indeces.len(),
+            });
+        }
+        for (position, index) in indeces.iter().enumerate() {
+            if *index >= num_choices {
+                return Err(ImprovementError::IndexOutOfRange {
+                    index: *index,
+                    num_choices,
+                });
+            }
+            if indeces[..position].contains(index) {
+                return Err(ImprovementError::DuplicateIndex(*index));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn choose_improvements(&mut self, indeces: &[usize]) -> Result<TurnOutcome, GameError> {
+        self.expect_phase(TurnPhase::ChoosingImprovement)?;
+        self.validate_improvement_choice(indeces)?;
+        self.log_action(GameAction::ChooseImprovements(indeces.to_vec()));
+        if let Some(ref set) = self.improvement_choice_set {
+            for given_idx in indeces.iter() {
+                self.run_stats.improvements_chosen.push(ChosenImprovement {
+                    improvement_type: set.improvement_type,
+                    description: set.displays[*given_idx].description.clone(),
+                });
+            }
+            match set.info {
+                ImprovementInfo::ShieldUpgradeInfo(ref vec_shield_upgrade) => {
+                    for given_idx in indeces.iter() {
+                        self.player.apply_upgrade(&vec_shield_upgrade[*given_idx]);
                     }
-                    ImprovementInfo::CoinPurchaseInfo(ref vec_coin_purchase) => {
-                        for given_idx in indeces.iter() {
-                            self.player.apply_purchase(&vec_coin_purchase[*given_idx]);
-                        }
+                }
+                ImprovementInfo::CoinPurchaseInfo(ref vec_coin_purchase) => {
+                    for given_idx in indeces.iter() {
+                        self.player.apply_purchase(&vec_coin_purchase[*given_idx]);
                     }
-                    ImprovementInfo::ExperiencePointLevelUpInfo(
-                        ref vec_experience_point_level_up,
-                    ) => {
-                        for given_idx in indeces.iter() {
-                            let lvl_up = &vec_experience_point_level_up[*given_idx];
-                            let maybe_ability_level = self.player.apply_level_up(lvl_up);
-                            if let ExperiencePointLevelUpInfo::Ability(atype) =
-                                lvl_up.experience_point_level_up_info
-                            {
-                                self.improvement_choice_set_generator
-                                    .ability_upgraded(atype, maybe_ability_level);
-                            }
+                }
+                ImprovementInfo::ExperiencePointLevelUpInfo(ref vec_experience_point_level_up) => {
+                    for given_idx in indeces.iter() {
+                        let lvl_up = &vec_experience_point_level_up[*given_idx];
+                        let maybe_ability_level = self
+                            .player
+                            .apply_level_up(lvl_up, &self.config.ruleset().ability_cooldowns);
+                        if let ExperiencePointLevelUpInfo::Ability(atype) =
+                            lvl_up.experience_point_level_up_info
+                        {
+                            self.improvement_choice_set_generator
+                                .ability_upgraded(atype, maybe_ability_level);
                         }
                     }
-                };
-            }
-            None => {}
+                }
+            };
         };
         self.step_improvement_queue();
+        let board_reshuffled = self.run_phases();
+        Ok(TurnOutcome {
+            hit: true,
+            phase: self.phase,
+            board_reshuffled,
+        })
     }
 
-    pub fn cast_ability(&mut self, index: usize) -> bool {
-        let ability_opt = &mut self.player.abilities[index];
+    pub fn cast_ability(&mut self, index: usize) -> Result<(), GameError> {
+        self.expect_phase(TurnPhase::AwaitingSelection)?;
+        let num_slots = self.player.abilities.len();
+        let ability_opt = match self.player.abilities.get_mut(index) {
+            Some(ability_opt) => ability_opt,
+            None => {
+                return Err(AbilityError::SlotOutOfRange {
+                    slot: index,
+                    num_slots,
+                }
+                .into())
+            }
+        };
         match ability_opt {
             Some(ref mut a) => {
                 if a.running_cooldown > 0 {
-                    return false;
+                    return Err(AbilityError::OnCooldown {
+                        remaining: a.running_cooldown,
+                    }
+                    .into());
                 }
+                let board_changed = matches!(
+                    a.ability_type,
+                    AbilityType::EnemiesToCoins | AbilityType::ScrambleBoard
+                );
                 match a.ability_type {
                     AbilityType::DoubleShieldCollection => {
                         self.collection_multipliers.shield_collection_multiplier *= 2
@@ -289,30 +871,800 @@ impl Game {
                             TileType::Coin,
                             &self.enemy,
                             &mut self.special_generator,
+                            &mut self.rng,
                         );
                     }
                     AbilityType::ScrambleBoard => {
-                        self.board.scramble();
+                        self.board.scramble(&mut self.rng);
                     }
                     AbilityType::COUNT => unreachable!(""),
                 };
                 a.put_on_cooldown();
-                true
+                self.events.push(GameEvent::AbilityCast {
+                    slot: index,
+                    ability_type: a.ability_type,
+                });
+                self.log_action(GameAction::CastAbility(index));
+                if board_changed {
+                    // either can leave the board without a path, same as a turn can
+                    self.reshuffle_if_dead();
+                }
+                Ok(())
             }
-            None => false,
+            None => Err(AbilityError::EmptySlot.into()),
         }
     }
 
-    pub fn apply_gravity_and_randomize_new_tiles(&mut self) {
-        self.board
-            .apply_gravity_and_randomize_new_tiles(&self.enemy, &mut self.special_generator);
+    fn apply_gravity_and_randomize_new_tiles(&mut self) {
+        self.board.apply_gravity_and_randomize_new_tiles(
+            &self.enemy,
+            &mut self.special_generator,
+            &mut self.rng,
+        );
+    }
+
+    pub fn legal_paths(&self, limits: PathLimits) -> LegalPaths<'_> {
+        self.board.legal_paths(limits)
+    }
+
+    pub fn has_legal_path(&self) -> bool {
+        self.board.has_legal_path()
+    }
+
+    pub fn board_view(&self) -> BoardView<'_> {
+        BoardView::new(&self.board)
+    }
+
+    // every tile movement since the last call, in the order they happened
+    pub fn take_tile_movements(&mut self) -> Vec<TileMovement> {
+        self.board.take_movements()
+    }
+
+    pub fn board_width(&self) -> usize {
+        self.board.width()
+    }
+
+    pub fn board_height(&self) -> usize {
+        self.board.height()
     }
 
     pub fn get_tile(&self, tile_position: &TilePosition) -> Option<Tile> {
-        self.board.get_tile(&tile_position)
+        self.board.get_tile(tile_position)
+    }
+
+    // where the tile with this id is now, None once it's been destroyed
+    pub fn tile_position(&self, id: TileId) -> Option<TilePosition> {
+        self.board.tile_position(id)
     }
 
     pub fn get_selection_start(&self) -> Option<TilePosition> {
         self.board.selection_start
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::error::SelectionError;
+    use crate::game::tile::Wind8;
+
+    fn testhelp_fingerprint(game: &mut Game) -> Vec<String> {
+        let mut fingerprint = vec![];
+        for y in 0..game.board_height() {
+            for x in 0..game.board_width() {
+                let tile = game
+                    .get_tile(&TilePosition::new(y as isize, x as isize))
+                    .expect("");
+                fingerprint.push(format!("{}", tile.tile_type as usize));
+            }
+        }
+        for (_, tile, id) in game.specials() {
+            if let tile::TileInfo::Special(special) = tile.tile_info {
+                fingerprint.push(format!("{} {}", id, special.special_type as usize));
+            }
+        }
+        for imp_type in [
+            ImprovementType::Shields,
+            ImprovementType::Coins,
+            ImprovementType::ExperiencePoints,
+        ] {
+            let set = game
+                .improvement_choice_set_generator
+                .get(imp_type, &mut game.rng);
+            for display in set.displays.iter() {
+                fingerprint.push(display.description.clone());
+            }
+        }
+        fingerprint
+    }
+
+    // every straight line of three tiles that connect with each other
+    pub fn testhelp_connected_lines(game: &Game) -> Vec<[TilePosition; 3]> {
+        let mut lines = vec![];
+        for y in 0..game.board_height() as isize {
+            for x in 0..game.board_width() as isize {
+                for w8_num in 0..8 {
+                    let step = TilePosition::from(Wind8::try_from(w8_num as u8).expect(""));
+                    let start = TilePosition::new(y, x);
+                    let line = [start, start + step, start + step + step];
+                    let tiles: Vec<_> = line.iter().filter_map(|p| game.get_tile(p)).collect();
+                    if tiles.len() == 3
+                        && tiles
+                            .iter()
+                            .all(|t| tiles[0].tile_type.connects_with(t.tile_type))
+                    {
+                        lines.push(line);
+                    }
+                }
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn test_same_seed_same_game() {
+        let mut a = Game::with_seed(1234);
+        let mut b = Game::with_seed(1234);
+        assert_eq!(a.seed(), 1234);
+        for _ in 0..10 {
+            assert_eq!(testhelp_fingerprint(&mut a), testhelp_fingerprint(&mut b));
+            a.board.scramble(&mut a.rng);
+            b.board.scramble(&mut b.rng);
+        }
+    }
+
+    #[test]
+    fn test_custom_config() {
+        let config = GameConfig::builder()
+            .seed(5)
+            .board_size(5, 7)
+            .ability_slots(2)
+            .player_start_stats(config::BeingStartStats {
+                base_output_damage: 1,
+                weapon_output_damage: 2,
+                hit_points: 10,
+                shields: 0,
+            })
+            .build()
+            .expect("");
+        let game = Game::new(config.clone());
+        assert_eq!(game.seed(), 5);
+        assert!(game.config() == &config);
+        assert_eq!(game.player().abilities.len(), 2);
+        assert_eq!(game.player().being.hit_points, 10);
+        assert_eq!((game.board_width(), game.board_height()), (5, 7));
+        assert!(game.get_tile(&TilePosition::new(6, 4)).is_some());
+        assert!(game.get_tile(&TilePosition::new(7, 0)).is_none());
+        assert!(game.get_tile(&TilePosition::new(0, 5)).is_none());
+
+        // unseeded configs get a seed picked for them
+        let game = Game::new(GameConfig::default());
+        assert_eq!(game.config().seed(), Some(game.seed()));
+    }
+
+    #[test]
+    fn test_difficulty_scales_new_enemies_and_specials() {
+        let mut game = Game::with_seed(8);
+        assert_eq!(game.difficulty_percent(), 100);
+        let start_template = *game.current_enemy_template();
+
+        game.turns_passed = 1000;
+        game.update_difficulty();
+        assert_eq!(game.difficulty_percent(), 300);
+        let template = game.current_enemy_template();
+        assert_eq!(template.hit_points, start_template.hit_points * 3);
+        assert_eq!(
+            template.base_output_damage,
+            start_template.base_output_damage * 3
+        );
+        // the special may be a different type, so compare against its type's base
+        let special = game.special_generator.get(&mut game.rng);
+        let mut base_game = Game::with_seed(8);
+        let base_special = Being::from((
+            special.special_type,
+            base_game.config.special_start_stats(),
+            &base_game.config.ruleset().special_scaling,
+            100,
+        ));
+        assert!(special.being.max_hit_points > base_special.max_hit_points);
+        base_game.update_difficulty();
+        assert!(base_game.current_enemy_template() == &start_template);
+    }
+
+    #[test]
+    fn test_dead_board_is_reshuffled() {
+        let config = GameConfig::builder()
+            .seed(4)
+            .board_size(2, 2)
+            .build()
+            .expect("");
+        let mut game = Game::new(config);
+        assert!(game.has_legal_path());
+        assert!(!game.reshuffle_if_dead());
+
+        // no three tiles connect in any order, so only a reroll can fix it
+        for (y, x, tile_type) in [
+            (0, 0, TileType::Potion),
+            (0, 1, TileType::Coin),
+            (1, 0, TileType::Shield),
+            (1, 1, TileType::Potion),
+        ] {
+            game.board.replace_tile(
+                &TilePosition::new(y, x),
+                tile_type,
+                &game.enemy,
+                &mut game.special_generator,
+                &mut game.rng,
+            );
+        }
+        assert!(!game.has_legal_path());
+        assert!(game.reshuffle_if_dead());
+        assert!(game.has_legal_path());
+    }
+
+    #[test]
+    fn test_unfixable_board_ends_run() {
+        // only 4 of a kind can be taken, which this seed never rolls
+        let config = GameConfig::builder()
+            .seed(3)
+            .board_size(2, 2)
+            .min_destruction_selection(4)
+            .tile_weights(config::TileWeights {
+                potion: 1,
+                shield: 1,
+                coin: 1,
+                sword: 1,
+                enemy: 0,
+            })
+            .build()
+            .expect("");
+        let mut game = Game::new(config);
+        assert!(!game.has_legal_path());
+        assert!(game.is_game_over());
+        assert_eq!(
+            game.run_summary().cause_of_death,
+            Some(CauseOfDeath::NoLegalPath)
+        );
+        assert_eq!(
+            game.take_events(),
+            vec![
+                GameEvent::BoardReshuffled,
+                GameEvent::PlayerDied(CauseOfDeath::NoLegalPath)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cast_ability_reshuffles_dead_board() {
+        for (ability_type, enemy_corner) in [
+            (AbilityType::ScrambleBoard, TileType::Coin),
+            (AbilityType::EnemiesToCoins, TileType::Enemy),
+        ] {
+            let config = GameConfig::builder()
+                .seed(4)
+                .board_size(2, 2)
+                .build()
+                .expect("");
+            let mut game = Game::new(config);
+            // the enemy only turns into a coin, which still connects with nothing
+            for (y, x, tile_type) in [
+                (0, 0, TileType::Potion),
+                (0, 1, enemy_corner),
+                (1, 0, TileType::Shield),
+                (1, 1, TileType::Potion),
+            ] {
+                game.board.replace_tile(
+                    &TilePosition::new(y, x),
+                    tile_type,
+                    &game.enemy,
+                    &mut game.special_generator,
+                    &mut game.rng,
+                );
+            }
+            game.take_events();
+            game.take_tile_movements();
+            let cooldown = game
+                .config()
+                .ruleset()
+                .ability_cooldowns
+                .cooldown(ability_type);
+            game.player.abilities[0] = Some(abilities::Ability::new(ability_type, cooldown));
+
+            game.cast_ability(0).expect("");
+            assert!(game.has_legal_path());
+            let events = game.take_events();
+            assert_eq!(
+                events[..2],
+                [
+                    GameEvent::AbilityCast {
+                        slot: 0,
+                        ability_type,
+                    },
+                    GameEvent::BoardReshuffled,
+                ]
+            );
+            // no order of these tiles has a path, so the board had to be rerolled
+            assert!(game
+                .take_tile_movements()
+                .iter()
+                .any(|m| matches!(m, TileMovement::Spawned { .. })));
+        }
+    }
+
+    #[test]
+    fn test_preview_matches_submitted_turn() {
+        for seed in 0..20 {
+            let mut game = Game::with_seed(seed);
+            let line = testhelp_connected_lines(&game)[0];
+            for tile_position in line.iter() {
+                game.select_tile(tile_position).expect("");
+            }
+            // the fingerprint draws from the rng, so compare hit points instead
+            let snapshot = |game: &Game| {
+                let mut hit_points = vec![game.player.being.hit_points];
+                for y in 0..game.board_height() as isize {
+                    for x in 0..game.board_width() as isize {
+                        let tile = game.get_tile(&TilePosition::new(y, x)).expect("");
+                        hit_points.push(match tile.tile_info {
+                            TileInfo::Enemy(being) => being.hit_points,
+                            TileInfo::Special(special) => special.being.hit_points,
+                            TileInfo::None => 0,
+                        });
+                    }
+                }
+                (hit_points, game.get_selection_start())
+            };
+            let before = snapshot(&game);
+            let preview = game.preview_selection();
+            assert!(before == snapshot(&game));
+            assert!(preview.hit);
+            assert!(preview.tiles.iter().map(|tp| tp.position).eq(line));
+
+            game.submit_turn().expect("");
+            let stats = &game.run_stats;
+            let num_destroyed = |tile_type| {
+                preview
+                    .tiles
+                    .iter()
+                    .filter(|tp| tp.destroyed && tp.tile_type == tile_type)
+                    .count()
+            };
+            assert_eq!(stats.enemies_killed, num_destroyed(TileType::Enemy));
+            assert_eq!(
+                stats.specials_killed.iter().sum::<usize>(),
+                num_destroyed(TileType::Special)
+            );
+            assert_eq!(stats.potions_collected, preview.potions);
+            assert_eq!(stats.shields_collected, preview.shields);
+            assert_eq!(stats.coins_collected, preview.coins);
+            assert_eq!(stats.level_ups, preview.level_ups);
+        }
+    }
+
+    #[test]
+    fn test_submit_turn_runs_whole_turn() {
+        let mut game = Game::with_seed(3);
+        let lines = testhelp_connected_lines(&game);
+        for tile_position in lines[0].iter() {
+            game.select_tile(tile_position).expect("");
+        }
+        let outcome = game.submit_turn().expect("");
+        assert!(outcome.hit);
+        assert_eq!(game.turns_passed, 1);
+        while game.phase() == TurnPhase::ChoosingImprovement {
+            let num_to_choose = game.improvement_choice_set().expect("").num_to_choose;
+            let indeces: Vec<usize> = (0..num_to_choose).collect();
+            game.choose_improvements(&indeces).expect("");
+        }
+        assert_eq!(game.phase(), TurnPhase::AwaitingSelection);
+        for y in 0..game.board_height() {
+            for x in 0..game.board_width() {
+                let tile = game
+                    .get_tile(&TilePosition::new(y as isize, x as isize))
+                    .expect("");
+                assert!(tile.tile_type != TileType::None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_actions_rejected_in_wrong_phase() {
+        let mut game = Game::with_seed(4);
+        assert_eq!(
+            game.choose_improvements(&[0]),
+            Err(GameError::WrongPhase(WrongPhase {
+                expected: TurnPhase::ChoosingImprovement,
+                actual: TurnPhase::AwaitingSelection,
+            }))
+        );
+
+        game.improvement_queue.push(ImprovementType::Coins);
+        game.step_improvement_queue();
+        game.phase = TurnPhase::ChoosingImprovement;
+        assert!(game.select_tile(&TilePosition::new(0, 0)).is_err());
+        assert!(game.cast_ability(0).is_err());
+        assert_eq!(
+            game.submit_turn(),
+            Err(GameError::WrongPhase(WrongPhase {
+                expected: TurnPhase::AwaitingSelection,
+                actual: TurnPhase::ChoosingImprovement,
+            }))
+        );
+        assert!(game.choose_improvements(&[0]).is_ok());
+        assert_ne!(game.phase(), TurnPhase::ChoosingImprovement);
+
+        game.phase = TurnPhase::GameOver;
+        assert!(game.submit_turn().is_err());
+        assert!(game.select_tile(&TilePosition::new(0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_select_tile_errors() {
+        let mut game = Game::with_seed(6);
+        assert_eq!(
+            game.select_tile(&TilePosition::new(-1, 0)),
+            Err(GameError::Selection(SelectionError::OutOfBounds))
+        );
+        assert_eq!(
+            game.select_tile(&TilePosition::new(0, DEFAULT_BOARD_WIDTH as isize)),
+            Err(GameError::Selection(SelectionError::OutOfBounds))
+        );
+        let line = testhelp_connected_lines(&game)[0];
+        game.select_tile(&line[0]).expect("");
+        assert_eq!(
+            game.select_tile(&(line[0] + (line[1] - line[0]) + (line[1] - line[0]))),
+            Err(GameError::Selection(SelectionError::NotAdjacent))
+        );
+        let start_type = game.get_tile(&line[0]).expect("").tile_type;
+        for w8_num in 0..8 {
+            let p = line[0] + TilePosition::from(Wind8::try_from(w8_num as u8).expect(""));
+            if let Some(tile) = game.get_tile(&p) {
+                if !start_type.connects_with(tile.tile_type) {
+                    assert_eq!(
+                        game.select_tile(&p),
+                        Err(GameError::Selection(SelectionError::TypesDontConnect))
+                    );
+                }
+            }
+        }
+        assert_eq!(game.action_log().len(), 1);
+    }
+
+    #[test]
+    fn test_backtrack_and_cancel_selection() {
+        let mut game = Game::with_seed(6);
+        assert_eq!(
+            game.backtrack_selection(),
+            Err(GameError::Selection(SelectionError::NothingSelected))
+        );
+        let line = testhelp_connected_lines(&game)[0];
+        for tile_position in line.iter() {
+            game.select_tile(tile_position).expect("");
+        }
+        assert_eq!(game.backtrack_selection(), Ok(line[2]));
+        assert!(game.selection_path() == line[..2]);
+
+        // dragging back onto the previous tile undoes the last step
+        game.select_tile(&line[2]).expect("");
+        game.select_tile(&line[1]).expect("");
+        assert!(game.selection_path() == line[..2]);
+        game.select_tile(&line[2]).expect("");
+
+        let replayed = replay::Replay::from_game(&game)
+            .run_to_end()
+            .expect("")
+            .selection_path();
+        assert!(replayed == game.selection_path());
+
+        game.cancel_selection().expect("");
+        assert!(game.selection_path().is_empty());
+        assert!(game.get_tile(&line[0]).expect("").next_selection == Wind8::None);
+        assert!(game.cancel_selection().is_ok());
+        assert_eq!(
+            game.backtrack_selection(),
+            Err(GameError::Selection(SelectionError::NothingSelected))
+        );
+    }
+
+    #[test]
+    fn test_submit_path() {
+        let mut game = Game::with_seed(6);
+        let line = testhelp_connected_lines(&game)[0];
+        let start_type = game.get_tile(&line[0]).expect("").tile_type;
+        let odd_one_out = (0..8)
+            .map(|w8_num| line[0] + TilePosition::from(Wind8::try_from(w8_num as u8).expect("")))
+            .find(|p| match game.get_tile(p) {
+                Some(tile) => !start_type.connects_with(tile.tile_type),
+                None => false,
+            })
+            .expect("");
+        game.select_tile(&line[0]).expect("");
+        let num_actions = game.action_log().len();
+
+        let invalid_paths: [(Vec<TilePosition>, SelectionError); 5] = [
+            (
+                vec![line[0], line[1], TilePosition::new(-1, 0)],
+                SelectionError::OutOfBounds,
+            ),
+            (vec![line[0], line[2], line[1]], SelectionError::NotAdjacent),
+            (
+                vec![line[1], line[0], odd_one_out],
+                SelectionError::TypesDontConnect,
+            ),
+            (
+                vec![line[0], line[1], line[0]],
+                SelectionError::RepeatedTile,
+            ),
+            (
+                vec![line[0], line[1]],
+                SelectionError::PathTooShort {
+                    length: 2,
+                    min_length: 3,
+                },
+            ),
+        ];
+        for (path, error) in invalid_paths.iter() {
+            assert_eq!(game.submit_path(path), Err(GameError::Selection(*error)));
+            assert!(game.selection_path() == [line[0]]);
+            assert_eq!(game.action_log().len(), num_actions);
+        }
+
+        let mut by_steps = Game::with_seed(6);
+        for tile_position in line.iter() {
+            by_steps.select_tile(tile_position).expect("");
+        }
+        let outcome = game.submit_path(&line).expect("");
+        assert!(outcome.hit);
+        assert_eq!(by_steps.submit_turn(), Ok(outcome));
+        assert_eq!(
+            testhelp_fingerprint(&mut game),
+            testhelp_fingerprint(&mut by_steps)
+        );
+    }
+
+    #[test]
+    fn test_cast_ability_errors() {
+        let mut game = Game::with_seed(7);
+        assert_eq!(
+            game.cast_ability(ABILITY_SLOTS),
+            Err(GameError::Ability(AbilityError::SlotOutOfRange {
+                slot: ABILITY_SLOTS,
+                num_slots: ABILITY_SLOTS,
+            }))
+        );
+        assert_eq!(
+            game.cast_ability(0),
+            Err(GameError::Ability(AbilityError::EmptySlot))
+        );
+        let cooldown = game
+            .config()
+            .ruleset()
+            .ability_cooldowns
+            .cooldown(AbilityType::ScrambleBoard);
+        game.player.abilities[0] = Some(abilities::Ability::new(
+            AbilityType::ScrambleBoard,
+            cooldown,
+        ));
+        game.cast_ability(0).expect("");
+        assert_eq!(
+            game.cast_ability(0),
+            Err(GameError::Ability(AbilityError::OnCooldown {
+                remaining: cooldown,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_choose_improvements_errors() {
+        let mut game = Game::with_seed(8);
+        game.improvement_queue.push(ImprovementType::Shields);
+        game.step_improvement_queue();
+        game.phase = TurnPhase::ChoosingImprovement;
+        assert_eq!(
+            game.choose_improvements(&[0, 1]),
+            Err(GameError::Improvement(ImprovementError::WrongCount {
+                expected: 1,
+                given: 2,
+            }))
+        );
+        assert_eq!(
+            game.choose_improvements(&[3]),
+            Err(GameError::Improvement(ImprovementError::IndexOutOfRange {
+                index: 3,
+                num_choices: 3,
+            }))
+        );
+
+        game.improvement_queue
+            .push(ImprovementType::ExperiencePoints);
+        game.step_improvement_queue();
+        assert_eq!(
+            game.choose_improvements(&[1, 1]),
+            Err(GameError::Improvement(ImprovementError::DuplicateIndex(1)))
+        );
+        assert!(game.action_log().is_empty());
+        game.choose_improvements(&[0, 1]).expect("");
+    }
+
+    #[test]
+    fn test_enemy_attack_ends_run() {
+        let mut game = Game::with_seed(10);
+        game.player.being.hit_points = 1;
+        game.player.being.shields = 0;
+        let lines = testhelp_connected_lines(&game);
+        let line = lines
+            .iter()
+            .find(|line| {
+                !game
+                    .get_tile(&line[0])
+                    .expect("")
+                    .tile_type
+                    .connects_with(TileType::Sword)
+            })
+            .expect("");
+        for tile_position in line.iter() {
+            game.select_tile(tile_position).expect("");
+        }
+        game.submit_turn().expect("");
+        while game.phase() == TurnPhase::ChoosingImprovement {
+            let num_to_choose = game.improvement_choice_set().expect("").num_to_choose;
+            let indeces: Vec<usize> = (0..num_to_choose).collect();
+            game.choose_improvements(&indeces).expect("");
+        }
+
+        assert!(game.is_game_over());
+        assert!(game.submit_turn().is_err());
+        assert!(game.select_tile(&TilePosition::new(0, 0)).is_err());
+        let summary = game.run_summary();
+        assert_eq!(summary.turns_survived, 1);
+        assert_eq!(summary.final_stats.hit_points, 0);
+        assert_eq!(summary.final_stats, game.player.being);
+        // a finished run summarizes the same every time
+        assert_eq!(game.run_summary(), summary);
+        assert!(matches!(
+            summary.cause_of_death,
+            Some(CauseOfDeath::EnemyAttack { .. })
+        ));
+    }
+
+    #[test]
+    fn test_kamikaze_explosion_ends_run() {
+        let mut game = Game::with_seed(11);
+        let p = TilePosition::new(2, 2);
+        game.board.replace_tile(
+            &p,
+            TileType::Special,
+            &game.enemy,
+            &mut game.special_generator,
+            &mut game.rng,
+        );
+        if let TileInfo::Special(ref mut special) = game.board.mut_tile_at(&p).tile_info {
+            special.special_type = SpecialType::Kamikaze;
+            special.special_info = special::SpecialInfo::Kamikaze(0);
+        }
+        game.player.being.hit_points = 1;
+        game.player.being.shields = 0;
+        game.phase = TurnPhase::SpecialsPhase;
+        game.run_phases();
+
+        assert!(game.is_game_over());
+        assert_eq!(
+            game.run_summary().cause_of_death,
+            Some(CauseOfDeath::KamikazeExplosion)
+        );
+        let events = game.take_events();
+        let exploded = events
+            .iter()
+            .position(
+                |e| matches!(e, GameEvent::KamikazeExploded { position, .. } if *position == p),
+            )
+            .expect("");
+        assert!(matches!(
+            events[exploded + 1..].first(),
+            Some(GameEvent::PlayerDamaged {
+                hit_points_lost: 1,
+                ..
+            })
+        ));
+        assert_eq!(
+            events.last(),
+            Some(&GameEvent::PlayerDied(CauseOfDeath::KamikazeExplosion))
+        );
+    }
+
+    #[test]
+    fn test_no_specials_run_after_kamikaze_kills() {
+        let mut game = Game::with_seed(11);
+        for (y, x, turns_until_kamikaze) in [(0, 0, 0), (0, 5, 0), (5, 0, 2)] {
+            let p = TilePosition::new(y, x);
+            game.board.replace_tile(
+                &p,
+                TileType::Special,
+                &game.enemy,
+                &mut game.special_generator,
+                &mut game.rng,
+            );
+            if let TileInfo::Special(ref mut special) = game.board.mut_tile_at(&p).tile_info {
+                special.special_type = SpecialType::Kamikaze;
+                special.special_info = special::SpecialInfo::Kamikaze(turns_until_kamikaze);
+            }
+        }
+        game.take_events();
+        game.player.being.hit_points = 1;
+        game.player.being.shields = 0;
+        game.phase = TurnPhase::SpecialsPhase;
+        game.run_phases();
+
+        assert!(game.is_game_over());
+        let events = game.take_events();
+        let exploded = events
+            .iter()
+            .position(|e| matches!(e, GameEvent::KamikazeExploded { .. }))
+            .expect("");
+        // only the explosion's own damage and refill can come between it and the death
+        for event in events[exploded + 1..events.len() - 1].iter() {
+            assert!(matches!(
+                event,
+                GameEvent::PlayerDamaged { .. } | GameEvent::SpecialSpawned { .. }
+            ));
+        }
+        assert_eq!(
+            events.last(),
+            Some(&GameEvent::PlayerDied(CauseOfDeath::KamikazeExplosion))
+        );
+    }
+
+    #[test]
+    fn test_events_follow_turn() {
+        let mut game = Game::with_seed(8);
+        let line = testhelp_connected_lines(&game)[0];
+        for tile_position in line.iter() {
+            game.select_tile(tile_position).expect("");
+        }
+        let preview = game.preview_selection();
+        game.submit_turn().expect("");
+        let events = game.take_events();
+        assert!(game.take_events().is_empty());
+
+        let hits: Vec<&GameEvent> = events
+            .iter()
+            .filter(|e| matches!(e, GameEvent::TileHit { .. }))
+            .collect();
+        assert_eq!(hits.len(), 3);
+        for (event, tile_preview) in hits.iter().zip(preview.tiles.iter()) {
+            match event {
+                GameEvent::TileHit {
+                    position,
+                    tile_type,
+                    damage,
+                    destroyed,
+                    ..
+                } => assert_eq!(
+                    (*position, *tile_type, *damage, *destroyed),
+                    (
+                        tile_preview.position,
+                        tile_preview.tile_type,
+                        tile_preview.damage,
+                        tile_preview.destroyed
+                    )
+                ),
+                _ => unreachable!(""),
+            }
+        }
+        assert!(events.contains(&GameEvent::TilesCollected {
+            potions: preview.potions,
+            hit_points_restored: preview.hit_points_restored,
+            shields: preview.shields,
+            coins: preview.coins,
+            experience_points: preview.experience_points,
+        }));
+        let num_queued = events
+            .iter()
+            .filter(|e| matches!(e, GameEvent::ImprovementQueued(_)))
+            .count();
+        assert_eq!(
+            num_queued,
+            preview.shield_upgrades + preview.coin_purchases + preview.level_ups
+        );
+    }
+}
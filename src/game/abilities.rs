@@ -1,4 +1,5 @@
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum AbilityType {
     DoubleShieldCollection,
@@ -44,19 +45,10 @@ impl AbilityType {
             Self::COUNT => unreachable!(""),
         }
     }
-
-    pub fn base_cooldown(self) -> usize {
-        match self {
-            Self::DoubleShieldCollection => 19,
-            Self::DoubleCoinCollection => 17,
-            Self::DoubleWeaponCollection => 15,
-            Self::EnemiesToCoins => 23,
-            Self::ScrambleBoard => 14,
-            Self::COUNT => unreachable!(""),
-        }
-    }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct Ability {
     pub ability_type: AbilityType,
     pub cooldown: AbilityCooldown,
@@ -65,10 +57,10 @@ pub struct Ability {
 }
 
 impl Ability {
-    pub fn new(ability_type: AbilityType) -> Self {
+    pub fn new(ability_type: AbilityType, cooldown: AbilityCooldown) -> Self {
         Self {
             ability_type,
-            cooldown: ability_type.base_cooldown(),
+            cooldown,
             running_cooldown: 0,
             current_level: 1,
         }
@@ -0,0 +1,258 @@
+use crate::game::error::GameError;
+use crate::game::legal_paths::PathLimits;
+use crate::game::preview::SelectionPreview;
+use crate::game::randomizer::{self, GameRng};
+use crate::game::tile::TilePosition;
+use crate::game::Game;
+
+// enumerating every path on a full board takes far too long, so the reference agents
+// only look at paths up to these lengths (or the minimum selection if that's longer)
+const RANDOM_AGENT_MAX_PATH_LENGTH: usize = 5;
+const RANDOM_AGENT_MAX_PATHS: usize = 1000;
+const GREEDY_AGENT_MAX_PATH_LENGTH: usize = 6;
+const GREEDY_AGENT_MAX_PATHS: usize = 5000;
+
+const DEFAULT_RANDOM_AGENT_CAST_PERCENT: usize = 25;
+
+// what an Agent wants to do next. paths are submitted whole, so an agent never has
+// to deal with a half built selection
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AgentAction {
+    SubmitPath(Vec<TilePosition>),
+    CastAbility(usize),
+    ChooseImprovements(Vec<usize>),
+}
+
+// a bot that plays a Game. only asked for an action while the game is waiting on the
+// player, i.e. in AwaitingSelection or ChoosingImprovement. None means there's nothing
+// the agent can do, which the game should never allow but a bot can't fix
+pub trait Agent {
+    fn choose_action(&mut self, game: &Game) -> Option<AgentAction>;
+}
+
+impl Game {
+    pub fn apply_agent_action(&mut self, action: &AgentAction) -> Result<(), GameError> {
+        match action {
+            AgentAction::SubmitPath(path) => self.submit_path(path).map(|_| ()),
+            AgentAction::CastAbility(index) => self.cast_ability(*index),
+            AgentAction::ChooseImprovements(indeces) => {
+                self.choose_improvements(indeces).map(|_| ())
+            }
+        }
+    }
+
+    // ability slots that can be cast right now
+    pub fn ready_abilities(&self) -> Vec<usize> {
+        self.player
+            .abilities
+            .iter()
+            .enumerate()
+            .filter_map(|(index, ability_opt)| match ability_opt {
+                Some(a) if a.running_cooldown == 0 => Some(index),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn max_path_length(game: &Game, max_path_length: usize) -> usize {
+    std::cmp::max(max_path_length, game.config().min_destruction_selection())
+}
+
+// without a path to take, casting is the only thing left that might change the board
+fn cast_without_path(game: &Game) -> Option<AgentAction> {
+    game.ready_abilities()
+        .first()
+        .map(|index| AgentAction::CastAbility(*index))
+}
+
+// picks uniformly between legal paths and improvements, and casts ready abilities now
+// and then. mostly useful as a baseline for other agents. on boards with more than
+// RANDOM_AGENT_MAX_PATHS paths it only picks between the first ones found
+pub struct RandomAgent {
+    rng: GameRng,
+    cast_percent: usize,
+}
+
+impl RandomAgent {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: randomizer::rng_from_seed(seed),
+            cast_percent: DEFAULT_RANDOM_AGENT_CAST_PERCENT,
+        }
+    }
+
+    // the chance of casting a ready ability instead of submitting a path
+    pub fn with_cast_percent(mut self, cast_percent: usize) -> Self {
+        self.cast_percent = cast_percent;
+        self
+    }
+
+    fn random_below(&mut self, below: usize) -> usize {
+        randomizer::evenly_distributed_random(below - 1, &mut self.rng)
+    }
+}
+
+impl Agent for RandomAgent {
+    fn choose_action(&mut self, game: &Game) -> Option<AgentAction> {
+        if let Some(set) = game.improvement_choice_set() {
+            let mut remaining: Vec<usize> = (0..set.displays.len()).collect();
+            let mut indeces = vec![];
+            while indeces.len() < set.num_to_choose && !remaining.is_empty() {
+                let pick = self.random_below(remaining.len());
+                indeces.push(remaining.swap_remove(pick));
+            }
+            return Some(AgentAction::ChooseImprovements(indeces));
+        }
+        let ready = game.ready_abilities();
+        if !ready.is_empty() && self.random_below(100) < self.cast_percent {
+            let pick = self.random_below(ready.len());
+            return Some(AgentAction::CastAbility(ready[pick]));
+        }
+        let mut paths: Vec<Vec<TilePosition>> = game
+            .legal_paths(PathLimits {
+                max_length: Some(max_path_length(game, RANDOM_AGENT_MAX_PATH_LENGTH)),
+                max_count: Some(RANDOM_AGENT_MAX_PATHS),
+            })
+            .collect();
+        if paths.is_empty() {
+            return cast_without_path(game);
+        }
+        let pick = self.random_below(paths.len());
+        Some(AgentAction::SubmitPath(paths.swap_remove(pick)))
+    }
+}
+
+// takes whichever path is worth the most this turn, counting damage dealt and
+// everything collected equally. abilities are cast when they make the best path
+// worth more, and improvements are taken in the order they're offered
+#[derive(Default)]
+pub struct GreedyAgent {}
+
+impl GreedyAgent {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn path_value(preview: &SelectionPreview) -> usize {
+        let damage: usize = preview.tiles.iter().map(|t| t.damage).sum();
+        damage
+            + preview.hit_points_restored
+            + preview.shields
+            + preview.coins
+            + preview.experience_points
+    }
+
+    fn best_path(game: &Game) -> Option<(Vec<TilePosition>, usize)> {
+        let mut best: Option<(Vec<TilePosition>, usize)> = None;
+        for path in game.legal_paths(PathLimits {
+            max_length: Some(max_path_length(game, GREEDY_AGENT_MAX_PATH_LENGTH)),
+            max_count: Some(GREEDY_AGENT_MAX_PATHS),
+        }) {
+            let value = match game.preview_path(&path) {
+                Ok(preview) => Self::path_value(&preview),
+                Err(_) => continue,
+            };
+            let is_better = match best {
+                Some((_, best_value)) => value > best_value,
+                None => true,
+            };
+            if is_better {
+                best = Some((path, value));
+            }
+        }
+        best
+    }
+}
+
+impl Agent for GreedyAgent {
+    fn choose_action(&mut self, game: &Game) -> Option<AgentAction> {
+        if let Some(set) = game.improvement_choice_set() {
+            let num_to_choose = std::cmp::min(set.num_to_choose, set.displays.len());
+            return Some(AgentAction::ChooseImprovements(
+                (0..num_to_choose).collect(),
+            ));
+        }
+        let (path, value) = match Self::best_path(game) {
+            Some(best) => best,
+            None => return cast_without_path(game),
+        };
+        // the clone carries the same rng, so a scramble is judged by the board it
+        // will really give
+        for index in game.ready_abilities() {
+            let mut after_cast = game.clone();
+            if after_cast.cast_ability(index).is_err() {
+                continue;
+            }
+            if let Some((_, value_after_cast)) = Self::best_path(&after_cast) {
+                if value_after_cast > value {
+                    return Some(AgentAction::CastAbility(index));
+                }
+            }
+        }
+        Some(AgentAction::SubmitPath(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::abilities::{Ability, AbilityType};
+    use crate::game::config::GameConfig;
+    use crate::game::tile::TileType;
+
+    // a 2x2 board that no order of its tiles can make a path on
+    fn testhelp_dead_board_game() -> Game {
+        let mut game = Game::new(
+            GameConfig::builder()
+                .seed(4)
+                .board_size(2, 2)
+                .build()
+                .expect(""),
+        );
+        for (y, x, tile_type) in [
+            (0, 0, TileType::Potion),
+            (0, 1, TileType::Coin),
+            (1, 0, TileType::Shield),
+            (1, 1, TileType::Potion),
+        ] {
+            game.board.replace_tile(
+                &TilePosition::new(y, x),
+                tile_type,
+                &game.enemy,
+                &mut game.special_generator,
+                &mut game.rng,
+            );
+        }
+        assert!(!game.has_legal_path());
+        game
+    }
+
+    #[test]
+    fn test_agents_without_a_path() {
+        let agents: [Box<dyn Agent>; 2] = [
+            Box::new(RandomAgent::new(0).with_cast_percent(0)),
+            Box::new(GreedyAgent::new()),
+        ];
+        for mut agent in agents {
+            // nothing to submit and nothing to cast
+            let mut game = testhelp_dead_board_game();
+            assert_eq!(agent.choose_action(&game), None);
+
+            // the cast leaves the board without a path until the game reshuffles it
+            let cooldown = game
+                .config()
+                .ruleset()
+                .ability_cooldowns
+                .cooldown(AbilityType::ScrambleBoard);
+            game.player.abilities[0] = Some(Ability::new(AbilityType::ScrambleBoard, cooldown));
+            let action = agent.choose_action(&game).expect("");
+            assert_eq!(action, AgentAction::CastAbility(0));
+            game.apply_agent_action(&action).expect("");
+            assert!(game.has_legal_path());
+            let action = agent.choose_action(&game).expect("");
+            assert!(matches!(action, AgentAction::SubmitPath(_)));
+            game.apply_agent_action(&action).expect("");
+        }
+    }
+}
@@ -1,13 +1,16 @@
+use crate::game::config::BeingStartStats;
 use crate::game::stat_modifiers::{ArmorPerShield, BaseDamageDecrease};
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum BeingType {
     Player,
     Enemy,
     Special,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Being {
     pub being_type: BeingType,
     pub base_output_damage: usize,
@@ -28,45 +31,61 @@ const ENEMY_START_HP: usize = 5;
 const ENEMY_START_SH: usize = 0;
 
 const BOSS_START_DMG: usize = 7;
+const BOSS_START_WEAPON_DMG: usize = 3;
 const BOSS_START_HP: usize = 20;
 const BOSS_START_SH: usize = 3;
 
-const MIN_BASE_DAMAGE: usize = 1;
+pub(crate) const MIN_BASE_DAMAGE: usize = 1;
 
-pub type BeingIsDead = bool;
-impl Being {
-    pub fn new(being_type: BeingType, scale_numerator: usize, scale_denominator: usize) -> Self {
+impl From<BeingType> for BeingStartStats {
+    fn from(being_type: BeingType) -> Self {
         match being_type {
             BeingType::Player => Self {
-                being_type,
-                base_output_damage: (PLAYER_START_BASE_DMG * scale_numerator) / scale_denominator,
-                weapon_output_damage: (PLAYER_START_WEAPON_DMG * scale_numerator)
-                    / scale_denominator,
-                hit_points: (PLAYER_START_HP * scale_numerator) / scale_denominator,
-                max_hit_points: (PLAYER_START_HP * scale_numerator) / scale_denominator,
-                shields: (PLAYER_START_SH * scale_numerator) / scale_denominator,
-                max_shields: (PLAYER_START_SH * scale_numerator) / scale_denominator,
+                base_output_damage: PLAYER_START_BASE_DMG,
+                weapon_output_damage: PLAYER_START_WEAPON_DMG,
+                hit_points: PLAYER_START_HP,
+                shields: PLAYER_START_SH,
             },
             BeingType::Enemy => Self {
-                being_type,
-                base_output_damage: (ENEMY_START_DMG * scale_numerator) / scale_denominator,
+                base_output_damage: ENEMY_START_DMG,
                 weapon_output_damage: 0,
-                hit_points: (ENEMY_START_HP * scale_numerator) / scale_denominator,
-                max_hit_points: (ENEMY_START_HP * scale_numerator) / scale_denominator,
-                shields: (ENEMY_START_SH * scale_numerator) / scale_denominator,
-                max_shields: (ENEMY_START_SH * scale_numerator) / scale_denominator,
+                hit_points: ENEMY_START_HP,
+                shields: ENEMY_START_SH,
             },
             BeingType::Special => Self {
-                being_type,
-                base_output_damage: (BOSS_START_DMG * scale_numerator) / scale_denominator,
-                weapon_output_damage: 3,
-                hit_points: (BOSS_START_HP * scale_numerator) / scale_denominator,
-                max_hit_points: (BOSS_START_HP * scale_numerator) / scale_denominator,
-                shields: (BOSS_START_SH * scale_numerator) / scale_denominator,
-                max_shields: (BOSS_START_SH * scale_numerator) / scale_denominator,
+                base_output_damage: BOSS_START_DMG,
+                weapon_output_damage: BOSS_START_WEAPON_DMG,
+                hit_points: BOSS_START_HP,
+                shields: BOSS_START_SH,
             },
         }
     }
+}
+
+pub type BeingIsDead = bool;
+impl Being {
+    pub fn new(
+        being_type: BeingType,
+        start_stats: &BeingStartStats,
+        scale_numerator: usize,
+        scale_denominator: usize,
+    ) -> Self {
+        let scale = |stat: usize| stat.saturating_mul(scale_numerator) / scale_denominator;
+        // only the player's weapon grows with scaling
+        let weapon_output_damage = match being_type {
+            BeingType::Player => scale(start_stats.weapon_output_damage),
+            BeingType::Enemy | BeingType::Special => start_stats.weapon_output_damage,
+        };
+        Self {
+            being_type,
+            base_output_damage: scale(start_stats.base_output_damage),
+            weapon_output_damage,
+            hit_points: scale(start_stats.hit_points),
+            max_hit_points: scale(start_stats.hit_points),
+            shields: scale(start_stats.shields),
+            max_shields: scale(start_stats.shields),
+        }
+    }
 
     pub fn take_damage(&mut self, mut damage: usize) -> BeingIsDead {
         if damage <= self.shields {
@@ -95,11 +114,11 @@ impl Being {
         let missing_hp = self.max_hit_points - self.hit_points;
         if hit_points_to_add <= missing_hp {
             self.hit_points += hit_points_to_add;
-            return 0;
+            0
         } else {
             hit_points_to_add -= missing_hp;
             self.hit_points = self.max_hit_points;
-            return hit_points_to_add;
+            hit_points_to_add
         }
     }
 
@@ -122,7 +141,7 @@ impl Being {
     }
 
     pub fn blunt(&mut self, blunting: BaseDamageDecrease) {
-        if self.base_output_damage - MIN_BASE_DAMAGE <= blunting {
+        if self.base_output_damage.saturating_sub(MIN_BASE_DAMAGE) <= blunting {
             self.base_output_damage = MIN_BASE_DAMAGE;
         } else {
             self.base_output_damage -= blunting;
@@ -1,26 +1,20 @@
 use crate::game::being::Being;
+use crate::game::config::{GameConfig, TileWeights};
+use crate::game::error::SelectionError;
+use crate::game::legal_paths::{LegalPaths, PathLimits};
+use crate::game::movement::TileMovement;
 use crate::game::player::Player;
+use crate::game::preview::SelectionHit;
 use crate::game::randomizer;
-use crate::game::randomizer::{Weight, WeightedRandomizer, WeightedRandomizerType};
-use crate::game::special::{SpecialGenerator, SpecialIdentifier};
+use crate::game::randomizer::{GameRng, WeightedRandomizer, WeightedRandomizerType};
+use crate::game::special::{Special, SpecialGenerator, SpecialIdentifier, SpecialInfo};
 use crate::game::stat_modifiers::BaseDamageDecrease;
-use crate::game::tile::{Tile, TileInfo, TilePosition, TileType, Wind8};
+use crate::game::tile::{Tile, TileId, TileInfo, TilePosition, TileType, Wind8};
 
-use std::io::Write;
-const _LOG_FILE: &str = "core_log.txt";
-fn _clear_logs() {
-    let mut file = std::fs::File::create(_LOG_FILE).expect("failed to create file");
-    write!(&mut file, "").expect("failed to write file");
-}
-fn _log(msg: &String) {
-    let mut file = std::fs::File::options()
-        .append(true)
-        .create(true)
-        .open(_LOG_FILE)
-        .expect("failed to create file");
-    writeln!(&mut file, "{}", msg).expect("failed to write file");
-}
+use std::hash::{Hash, Hasher};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
 pub struct Board {
     w: usize,
     h: usize,
@@ -28,61 +22,120 @@ pub struct Board {
     tiles: Vec<Vec<Tile>>,
     tile_randomizer: WeightedRandomizer,
     num_specials: usize,
+    min_destruction_selection: usize,
+    tile_weights: TileWeights,
     pub selection_start: Option<TilePosition>,
+    // 0 is left for empty tiles
+    unused_tile_id: TileId,
+    // waiting to be taken by the frontend, not part of the game state
+    #[cfg_attr(feature = "serde", serde(skip))]
+    movements: Vec<TileMovement>,
+}
+
+// movements are left out for the same reason they aren't saved
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Board {
+            w,
+            h,
+            tiles,
+            tile_randomizer,
+            num_specials,
+            min_destruction_selection,
+            tile_weights,
+            selection_start,
+            unused_tile_id,
+            movements: _,
+        } = self;
+        w.hash(state);
+        h.hash(state);
+        tiles.hash(state);
+        tile_randomizer.hash(state);
+        num_specials.hash(state);
+        min_destruction_selection.hash(state);
+        tile_weights.hash(state);
+        selection_start.hash(state);
+        unused_tile_id.hash(state);
+    }
 }
 
-const MIN_DESTRUCTION_SELECTION: usize = 3;
 const WR_EXP_ERR_STR: &str =
     "weighted_random should only return None if nothing has been added to the randomizer";
 const TT_EXP_ERR_STR: &str = "TileType::TryFrom<usize> shouldn't fail because the usize is from a WeightedRandomizer with only the TileType's added";
 
 impl Board {
     pub fn new(
-        w: usize,
-        h: usize,
+        config: &GameConfig,
         enemy: &Being,
         special_generator: &mut SpecialGenerator,
+        rng: &mut GameRng,
     ) -> Board {
-        _clear_logs();
-
         // tile randomizer
 
         let mut tile_randomizer = WeightedRandomizer::default();
         for tt in 0..(TileType::COUNT as usize) {
             let tile_type =
                 TileType::try_from(tt).expect("TileType::try_from errored where it never should");
-            let default_weight = Weight::try_from(tile_type)
-                .expect("Weight::try_from errored where it never should");
-            tile_randomizer.set_weight(tt, default_weight);
+            tile_randomizer.set_weight(tt, config.tile_weights().weight(tile_type));
         }
-        // DEBUG: always start with a boss
-        tile_randomizer.set_weight(TileType::Special as usize, 1000);
 
         // create the board
 
+        let w = config.board_width();
+        let h = config.board_height();
         let mut b = Self {
             w,
             h,
             tiles: vec![],
             tile_randomizer,
             num_specials: 0,
+            min_destruction_selection: config.min_destruction_selection(),
+            tile_weights: *config.tile_weights(),
             selection_start: None,
+            unused_tile_id: 1,
+            movements: vec![],
         };
 
         // tiles
 
-        for _ in 0..w {
+        for _ in 0..h {
             let new_idx = b.tiles.len();
             b.tiles.push(vec![]);
-            for _ in 0..h {
+            for _ in 0..w {
                 b.tiles[new_idx].push(Tile::default());
             }
         }
-        b.apply_gravity_and_randomize_new_tiles(enemy, special_generator);
+        b.apply_gravity_and_randomize_new_tiles(enemy, special_generator, rng);
+        // the starting board isn't a change anyone needs to animate
+        b.movements.clear();
 
         b
     }
 
+    fn new_tile(&mut self, tile_type: TileType, tile_info: TileInfo) -> Tile {
+        let tile = Tile::new(self.unused_tile_id, tile_type, tile_info);
+        self.unused_tile_id += 1;
+        tile
+    }
+
+    pub fn tile_position(&self, id: TileId) -> Option<TilePosition> {
+        if id == 0 {
+            return None;
+        }
+        for (y, row) in self.tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                if tile.id == id {
+                    return Some(TilePosition::new(y as isize, x as isize));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn take_movements(&mut self) -> Vec<TileMovement> {
+        std::mem::take(&mut self.movements)
+    }
+
     pub fn incoming_damage(&self) -> usize {
         let mut dmg = 0;
         for col in self.tiles.iter() {
@@ -93,6 +146,30 @@ impl Board {
         dmg
     }
 
+    pub fn width(&self) -> usize {
+        self.w
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn min_destruction_selection(&self) -> usize {
+        self.min_destruction_selection
+    }
+
+    pub fn legal_paths(&self, limits: PathLimits) -> LegalPaths<'_> {
+        LegalPaths::new(self, limits)
+    }
+
+    pub fn has_legal_path(&self) -> bool {
+        let limits = PathLimits {
+            max_length: Some(self.min_destruction_selection),
+            max_count: Some(1),
+        };
+        self.legal_paths(limits).next().is_some()
+    }
+
     pub fn num_tiles(&self) -> usize {
         self.w * self.h
     }
@@ -107,14 +184,14 @@ impl Board {
 
     pub fn specials(
         &self,
-        omit_ids: &Vec<SpecialIdentifier>,
+        omit_ids: &[SpecialIdentifier],
     ) -> Vec<(TilePosition, Tile, SpecialIdentifier)> {
         if self.num_specials <= omit_ids.len() {
             return vec![];
         }
         let mut specials_vec = Vec::with_capacity(self.num_specials);
-        for (y, col) in self.tiles.iter().enumerate() {
-            for (x, tile) in col.iter().enumerate() {
+        for (y, row) in self.tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
                 if tile.tile_type == TileType::Special {
                     if let TileInfo::Special(special) = tile.tile_info {
                         if !omit_ids.contains(&special.id) {
@@ -136,32 +213,34 @@ impl Board {
         unreachable!("self.num_specials and the number of specials found in the tiles differ");
     }
 
-    pub fn select_tile(&mut self, position_to_select: &TilePosition) -> bool {
+    // selecting a tile that's already selected cuts the selection back to end at it,
+    // so dragging back onto the previous tile undoes the last step
+    pub fn select_tile(&mut self, position_to_select: &TilePosition) -> Result<(), SelectionError> {
+        if !self.position_valid(position_to_select) {
+            return Err(SelectionError::OutOfBounds);
+        }
         match self.selection_start {
             Some(ref pos) => {
                 let start_tile_type = self.tile_at(pos).tile_type;
                 if !start_tile_type.connects_with(self.tile_at(position_to_select).tile_type) {
-                    return false;
+                    return Err(SelectionError::TypesDontConnect);
                 }
                 let mut p: TilePosition = *pos;
                 let num_tiles = self.num_tiles();
                 for _ in 0..num_tiles {
                     if p == *position_to_select {
                         self.remove_selection_starting_at(&p);
-                        return true;
+                        return Ok(());
                     }
                     let relative_next = self.tile_at(&p).next_selection;
                     match relative_next {
                         Wind8::None => {
-                            match Wind8::try_from(*position_to_select - p) {
-                                Ok(w8) => match w8 {
-                                    Wind8::None => return false,
-                                    _ => {
-                                        self.mut_tile_at(&p).next_selection = w8;
-                                        return true;
-                                    }
-                                },
-                                Err(_) => return false,
+                            return match Wind8::try_from(*position_to_select - p) {
+                                Ok(Wind8::None) | Err(_) => Err(SelectionError::NotAdjacent),
+                                Ok(w8) => {
+                                    self.mut_tile_at(&p).next_selection = w8;
+                                    Ok(())
+                                }
                             };
                         }
                         _ => {
@@ -173,16 +252,115 @@ impl Board {
             }
             None => {
                 self.selection_start = Some(*position_to_select);
-                true
+                Ok(())
             }
         }
     }
 
-    pub fn drop_selection(
-        &mut self,
+    // checks a whole path the way select_tile would check it one tile at a time,
+    // and also that it's long enough to hit
+    pub fn validate_path(&self, path: &[TilePosition]) -> Result<(), SelectionError> {
+        for (i, p) in path.iter().enumerate() {
+            if !self.position_valid(p) {
+                return Err(SelectionError::OutOfBounds);
+            }
+            if path[..i].contains(p) {
+                return Err(SelectionError::RepeatedTile);
+            }
+            if i == 0 {
+                continue;
+            }
+            if !self
+                .tile_at(&path[0])
+                .tile_type
+                .connects_with(self.tile_at(p).tile_type)
+            {
+                return Err(SelectionError::TypesDontConnect);
+            }
+            match Wind8::try_from(*p - path[i - 1]) {
+                Ok(Wind8::None) | Err(_) => return Err(SelectionError::NotAdjacent),
+                Ok(_) => {}
+            }
+        }
+        if path.len() < self.min_destruction_selection {
+            return Err(SelectionError::PathTooShort {
+                length: path.len(),
+                min_length: self.min_destruction_selection,
+            });
+        }
+        Ok(())
+    }
+
+    // the tiles select_tile would have to be given, in order, to extend the selection
+    // to position_to_select in a straight or diagonal line. for when a fast drag
+    // skips over tiles
+    pub fn gap_fill(
+        &self,
+        position_to_select: &TilePosition,
+    ) -> Result<Vec<TilePosition>, SelectionError> {
+        if !self.position_valid(position_to_select) {
+            return Err(SelectionError::OutOfBounds);
+        }
+        let selection = self.selection();
+        let last = match selection.last() {
+            Some(last) => *last,
+            None => return Ok(vec![*position_to_select]),
+        };
+        // cutting the selection back works the same as without filling
+        if selection.contains(position_to_select) {
+            return Ok(vec![*position_to_select]);
+        }
+        let d = *position_to_select - last;
+        if d.y != 0 && d.x != 0 && d.y.abs() != d.x.abs() {
+            return Err(SelectionError::NotInLine);
+        }
+        let step = TilePosition::new(d.y.signum(), d.x.signum());
+        let start_tile_type = self.tile_at(&selection[0]).tile_type;
+        let mut gap = vec![];
+        let mut p = last;
+        while p != *position_to_select {
+            p = p + step;
+            if selection.contains(&p) {
+                return Err(SelectionError::RepeatedTile);
+            }
+            if !start_tile_type.connects_with(self.tile_at(&p).tile_type) {
+                return Err(SelectionError::TypesDontConnect);
+            }
+            gap.push(p);
+        }
+        Ok(gap)
+    }
+
+    // removes the last selected tile, returning where it was
+    pub fn backtrack_selection(&mut self) -> Result<TilePosition, SelectionError> {
+        let selection = self.selection();
+        match selection.len() {
+            0 => Err(SelectionError::NothingSelected),
+            1 => {
+                self.selection_start = None;
+                Ok(selection[0])
+            }
+            len => {
+                self.mut_tile_at(&selection[len - 2]).next_selection = Wind8::None;
+                Ok(selection[len - 1])
+            }
+        }
+    }
+
+    pub fn cancel_selection(&mut self) {
+        if let Some(pos) = self.selection_start {
+            self.remove_selection_starting_at(&pos);
+            self.selection_start = None;
+        }
+    }
+
+    // what drop_selection would do to each selected tile, without doing it.
+    // tiles are only hit if the selection is long enough to hit
+    pub fn hit_selection(
+        &self,
         player: &Player,
         weapon_collection_multiplier: usize,
-    ) -> (bool, Vec<Tile>) {
+    ) -> (bool, Vec<SelectionHit>) {
         let hit = self.selection_hits();
         let (num_weapons, num_beings) = if hit {
             let (nw, nb) = self.num_weapons_and_beings_in_selection();
@@ -190,37 +368,97 @@ impl Board {
         } else {
             (0, 0)
         };
-        let mut destructing_tiles: Vec<Tile> = vec![];
-        match self.selection_start {
-            Some(pos) => {
-                self.selection_start = None;
-                let mut p = pos;
-                let num_tiles = self.num_tiles();
-                let mut found_the_end = false;
-                for _ in 0..num_tiles {
-                    let relative_next = self.tile_at(&p).next_selection;
-                    if hit
-                        && self
-                            .mut_tile_at(&p)
-                            .hit(player.output_damage(num_beings, num_weapons))
-                    {
-                        destructing_tiles.push(*self.tile_at(&p));
-                        self.destroy_tile(&p);
-                    }
-                    self.mut_tile_at(&p).next_selection = Wind8::None;
-                    match relative_next {
-                        Wind8::None => {
-                            found_the_end = true;
-                            break;
-                        }
-                        _ => p = p + TilePosition::from(relative_next),
+        let damage = player.output_damage(num_beings, num_weapons);
+        let mut selection_hits: Vec<SelectionHit> = vec![];
+        if let Some(pos) = self.selection_start {
+            let mut p = pos;
+            let num_tiles = self.num_tiles();
+            let mut found_the_end = false;
+            for _ in 0..num_tiles {
+                let mut tile = *self.tile_at(&p);
+                let was_reanimated = Self::undead_reanimated(&tile);
+                let (tile_damage, destroyed) = if hit {
+                    let tile_damage = match tile.tile_info {
+                        TileInfo::Enemy(_) | TileInfo::Special(_) => damage,
+                        TileInfo::None => 0,
                     };
-                }
-                assert!(found_the_end);
+                    (tile_damage, tile.hit(damage))
+                } else {
+                    (0, false)
+                };
+                selection_hits.push(SelectionHit {
+                    position: p,
+                    tile,
+                    damage: tile_damage,
+                    destroyed,
+                    reanimated: !was_reanimated && Self::undead_reanimated(&tile),
+                });
+                match tile.next_selection {
+                    Wind8::None => {
+                        found_the_end = true;
+                        break;
+                    }
+                    relative_next => p = p + TilePosition::from(relative_next),
+                };
+            }
+            assert!(found_the_end);
+        }
+        (hit, selection_hits)
+    }
+
+    fn undead_reanimated(tile: &Tile) -> bool {
+        matches!(
+            tile.tile_info,
+            TileInfo::Special(Special {
+                special_info: SpecialInfo::Undead(true),
+                ..
+            })
+        )
+    }
+
+    // returns every selected tile after being hit, in selection order
+    pub fn drop_selection(
+        &mut self,
+        player: &Player,
+        weapon_collection_multiplier: usize,
+    ) -> (bool, Vec<SelectionHit>) {
+        let (hit, selection_hits) = self.hit_selection(player, weapon_collection_multiplier);
+        self.selection_start = None;
+        for selection_hit in selection_hits.iter() {
+            let p = selection_hit.position;
+            if selection_hit.destroyed {
+                self.destroy_tile(&p);
+            } else {
+                *self.mut_tile_at(&p) = selection_hit.tile;
             }
-            None => {}
+            self.mut_tile_at(&p).next_selection = Wind8::None;
         }
-        (hit, destructing_tiles)
+        (hit, selection_hits)
+    }
+
+    pub fn tile_ref(&self, tile_position: &TilePosition) -> Option<&Tile> {
+        if self.position_valid(tile_position) {
+            Some(self.tile_at(tile_position))
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Tile]> {
+        self.tiles.iter().map(|row| row.as_slice())
+    }
+
+    pub fn selection(&self) -> Vec<TilePosition> {
+        let mut selection = vec![];
+        let mut next = self.selection_start;
+        while let Some(p) = next {
+            selection.push(p);
+            next = match self.tile_at(&p).next_selection {
+                Wind8::None => None,
+                relative_next => Some(p + TilePosition::from(relative_next)),
+            };
+        }
+        selection
     }
 
     pub fn get_tile(&self, tile_position: &TilePosition) -> Option<Tile> {
@@ -232,8 +470,8 @@ impl Board {
     }
 
     pub fn apply_blunting(&mut self, blunting: BaseDamageDecrease) {
-        for col in self.tiles.iter_mut() {
-            for tile in col.iter_mut() {
+        for row in self.tiles.iter_mut() {
+            for tile in row.iter_mut() {
                 match tile.tile_info {
                     TileInfo::Enemy(ref mut b) => b.blunt(blunting),
                     TileInfo::Special(ref mut s) => s.blunt(blunting),
@@ -247,6 +485,7 @@ impl Board {
         &mut self,
         enemy: &Being,
         special_generator: &mut SpecialGenerator,
+        rng: &mut GameRng,
     ) {
         for x in 0..self.w {
             let mut num_falling = 0;
@@ -257,6 +496,10 @@ impl Board {
                         if num_falling > 0 {
                             self.tiles[y + num_falling][x] = self.tiles[y][x];
                             self.tiles[y][x] = Tile::default();
+                            self.movements.push(TileMovement::Fell {
+                                from: TilePosition::new(y as isize, x as isize),
+                                to: TilePosition::new((y + num_falling) as isize, x as isize),
+                            });
                         }
                     }
                 };
@@ -265,7 +508,7 @@ impl Board {
                 let y = num_falling - i - 1;
                 let tile_type = TileType::try_from(
                     self.tile_randomizer
-                        .weighted_random()
+                        .weighted_random(rng)
                         .expect(WR_EXP_ERR_STR),
                 )
                 .expect(TT_EXP_ERR_STR);
@@ -275,9 +518,14 @@ impl Board {
                     self.tile_randomizer
                         .set_weight(TileType::Special as usize, 0);
                 }
-                let tile_info = TileInfo::from((tile_type, enemy, &mut *special_generator));
+                let tile_info =
+                    TileInfo::from((tile_type, enemy, &mut *special_generator, &mut *rng));
                 self.meta_create_tile(tile_type);
-                self.tiles[y][x] = Tile::new(tile_type, tile_info);
+                self.tiles[y][x] = self.new_tile(tile_type, tile_info);
+                self.movements.push(TileMovement::Spawned {
+                    at: TilePosition::new(y as isize, x as isize),
+                    tile: self.tiles[y][x],
+                });
             }
         }
     }
@@ -291,7 +539,7 @@ impl Board {
         // The idea is that we scale the special's weight with the number of turns that have passed
         // along with the the number of turns that have passed since the most recent special kill.
         // Also, if a special enemy already exists, we drop the weight considerably
-        let enemy_weight = Weight::try_from(TileType::Enemy).expect("");
+        let enemy_weight = self.tile_weights.weight(TileType::Enemy);
         let special_div_set = if self.num_specials > 0 {
             (5, 6)
         } else {
@@ -350,7 +598,7 @@ impl Board {
                         _ => p = p + TilePosition::from(relative_next),
                     };
                     num_tiles += 1;
-                    if num_tiles >= MIN_DESTRUCTION_SELECTION {
+                    if num_tiles >= self.min_destruction_selection {
                         return true;
                     }
                 }
@@ -411,34 +659,31 @@ impl Board {
     fn num_weapons_and_beings_in_selection(&self) -> (usize, usize) {
         let mut num_weapons: usize = 0;
         let mut num_beings: usize = 0;
-        match self.selection_start {
-            Some(ref pos) => {
-                if !self.tile_at(pos).tile_type.connects_with(TileType::Sword) {
-                    return (0, 0);
-                }
-                let mut p = *pos;
-                let num_tiles = self.num_tiles();
-                let mut found_the_end = false;
-                for _ in 0..num_tiles {
-                    match self.tile_at(&p).tile_type {
-                        TileType::Sword => num_weapons += 1,
-                        TileType::Enemy | TileType::Special => num_beings += 1,
-                        _ => {}
-                    };
-                    let relative_next = self.tile_at(&p).next_selection;
-                    match relative_next {
-                        Wind8::None => {
-                            found_the_end = true;
-                            break;
-                        }
-                        _ => {
-                            p = p + TilePosition::from(relative_next);
-                        }
-                    };
-                }
-                assert!(found_the_end);
+        if let Some(ref pos) = self.selection_start {
+            if !self.tile_at(pos).tile_type.connects_with(TileType::Sword) {
+                return (0, 0);
             }
-            None => {}
+            let mut p = *pos;
+            let num_tiles = self.num_tiles();
+            let mut found_the_end = false;
+            for _ in 0..num_tiles {
+                match self.tile_at(&p).tile_type {
+                    TileType::Sword => num_weapons += 1,
+                    TileType::Enemy | TileType::Special => num_beings += 1,
+                    _ => {}
+                };
+                let relative_next = self.tile_at(&p).next_selection;
+                match relative_next {
+                    Wind8::None => {
+                        found_the_end = true;
+                        break;
+                    }
+                    _ => {
+                        p = p + TilePosition::from(relative_next);
+                    }
+                };
+            }
+            assert!(found_the_end);
         };
         (num_weapons, num_beings)
     }
@@ -451,6 +696,10 @@ impl Board {
 
     fn destroy_tile(&mut self, tile_pos: &TilePosition) {
         self.meta_destroy_tile(tile_pos);
+        self.movements.push(TileMovement::Destroyed {
+            at: *tile_pos,
+            tile: *self.tile_at(tile_pos),
+        });
         *self.mut_tile_at(tile_pos) = Tile::default();
     }
 
@@ -460,20 +709,25 @@ impl Board {
         }
     }
 
+    // row major, same as tiles
     fn serialize_tile_position(&self, tile_pos: &TilePosition) -> usize {
-        tile_pos.y as usize + tile_pos.x as usize * self.h
+        tile_pos.y as usize * self.w + tile_pos.x as usize
     }
 
     fn deserialize_tile_position(&self, s_tile_pos: usize) -> TilePosition {
         TilePosition::new(
-            (s_tile_pos % self.h) as isize,
-            (s_tile_pos / self.h) as isize,
+            (s_tile_pos / self.w) as isize,
+            (s_tile_pos % self.w) as isize,
         )
     }
 
     // special end of turn
 
-    pub fn random_tile_of_type(&self, tile_type: TileType) -> Option<TilePosition> {
+    pub fn random_tile_of_type(
+        &self,
+        tile_type: TileType,
+        rng: &mut GameRng,
+    ) -> Option<TilePosition> {
         let mut randomizer = WeightedRandomizer::default();
         for y in 0..self.h {
             for x in 0..self.w {
@@ -485,10 +739,9 @@ impl Board {
                 }
             }
         }
-        match randomizer.weighted_random() {
-            Some(s_tile_pos) => Some(self.deserialize_tile_position(s_tile_pos)),
-            None => None,
-        }
+        randomizer
+            .weighted_random(rng)
+            .map(|s_tile_pos| self.deserialize_tile_position(s_tile_pos))
     }
 
     pub fn replace_tile(
@@ -497,11 +750,18 @@ impl Board {
         replace_type: TileType,
         enemy: &Being,
         special_generator: &mut SpecialGenerator,
+        rng: &mut GameRng,
     ) {
-        let tile_info = TileInfo::from((replace_type, enemy, &mut *special_generator));
+        let tile_info = TileInfo::from((replace_type, enemy, &mut *special_generator, rng));
         self.meta_destroy_tile(tile_pos);
         self.meta_create_tile(replace_type);
-        *self.mut_tile_at(tile_pos) = Tile::new(replace_type, tile_info);
+        let old = *self.tile_at(tile_pos);
+        *self.mut_tile_at(tile_pos) = self.new_tile(replace_type, tile_info);
+        self.movements.push(TileMovement::Replaced {
+            at: *tile_pos,
+            old,
+            new: *self.tile_at(tile_pos),
+        });
         self.enforce_selection_valid();
     }
 
@@ -509,14 +769,16 @@ impl Board {
         let tmp = *self.tile_at(tp2);
         *self.mut_tile_at(tp2) = *self.tile_at(tp1);
         *self.mut_tile_at(tp1) = tmp;
+        self.movements
+            .push(TileMovement::Swapped { a: *tp1, b: *tp2 });
         self.enforce_selection_valid();
     }
 
-    pub fn swap_position_with_random_other(&mut self, tp: &TilePosition) {
+    pub fn swap_position_with_random_other(&mut self, tp: &TilePosition, rng: &mut GameRng) {
         let serialized_tp = self.serialize_tile_position(tp);
         // get value in [0, num_tiles - 2]
         let mut serialized_random_other_tp =
-            randomizer::evenly_distributed_random(self.num_tiles() - 2);
+            randomizer::evenly_distributed_random(self.num_tiles() - 2, rng);
         // map serialized_tp to self.num_tiles() - 1
         if serialized_random_other_tp == serialized_tp {
             serialized_random_other_tp = self.num_tiles() - 1;
@@ -547,6 +809,7 @@ impl Board {
         center_pos: &TilePosition,
         enemy: &Being,
         special_generator: &mut SpecialGenerator,
+        rng: &mut GameRng,
     ) -> Vec<Tile> {
         let mut destroyed_tiles = Vec::with_capacity(3 * 3);
         for w8_num in 0..8 {
@@ -559,7 +822,7 @@ impl Board {
         }
         destroyed_tiles.push(*self.tile_at(center_pos));
         self.destroy_tile(center_pos);
-        self.apply_gravity_and_randomize_new_tiles(enemy, special_generator);
+        self.apply_gravity_and_randomize_new_tiles(enemy, special_generator, rng);
         self.enforce_selection_valid();
         destroyed_tiles
     }
@@ -572,22 +835,50 @@ impl Board {
         to: TileType,
         enemy: &Being,
         special_generator: &mut SpecialGenerator,
+        rng: &mut GameRng,
     ) {
         for y in 0..self.h {
             for x in 0..self.w {
                 let p = TilePosition::new(y as isize, x as isize);
                 if self.tile_at(&p).tile_type == from {
-                    let tile_info = TileInfo::from((to, enemy, &mut *special_generator));
+                    let tile_info = TileInfo::from((to, enemy, &mut *special_generator, &mut *rng));
                     self.meta_destroy_tile(&p);
                     self.meta_create_tile(to);
-                    *self.mut_tile_at(&p) = Tile::new(to, tile_info);
+                    let old = *self.tile_at(&p);
+                    *self.mut_tile_at(&p) = self.new_tile(to, tile_info);
+                    self.movements.push(TileMovement::Replaced {
+                        at: p,
+                        old,
+                        new: *self.tile_at(&p),
+                    });
                 }
             }
         }
         self.enforce_selection_valid();
     }
 
-    pub fn scramble(&mut self) {
+    // replaces every tile other than specials with a new random one
+    pub fn reroll(
+        &mut self,
+        enemy: &Being,
+        special_generator: &mut SpecialGenerator,
+        rng: &mut GameRng,
+    ) {
+        self.selection_start = None;
+        for y in 0..self.h {
+            for x in 0..self.w {
+                let p = TilePosition::new(y as isize, x as isize);
+                if self.tile_at(&p).tile_type == TileType::Special {
+                    self.mut_tile_at(&p).next_selection = Wind8::None;
+                } else {
+                    self.destroy_tile(&p);
+                }
+            }
+        }
+        self.apply_gravity_and_randomize_new_tiles(enemy, special_generator, rng);
+    }
+
+    pub fn scramble(&mut self, rng: &mut GameRng) {
         // oh boy here we go
         self.selection_start = None;
         let mut randomizer = WeightedRandomizer::new(WeightedRandomizerType::MetaSubAllOnObtain);
@@ -595,18 +886,23 @@ impl Board {
         for val in 0..num_tiles {
             randomizer.set_weight(val, 1);
         }
-        let first_idx_2d = randomizer.weighted_random().expect("");
+        let first_idx_2d = randomizer.weighted_random(rng).expect("");
         let first_pos: TilePosition = self.deserialize_tile_position(first_idx_2d);
         let mut first = *self.tile_at(&first_pos);
         first.next_selection = Wind8::None;
         let mut target_pos = first_pos;
         for _ in 0..num_tiles {
-            let value_opt = randomizer.weighted_random();
+            let value_opt = randomizer.weighted_random(rng);
             match value_opt {
                 Some(value) => {
                     let rand_tile_pos = self.deserialize_tile_position(value);
                     *self.mut_tile_at(&target_pos) = *self.tile_at(&rand_tile_pos);
                     self.mut_tile_at(&target_pos).next_selection = Wind8::None;
+                    // swapping along the cycle in order ends up with the same board
+                    self.movements.push(TileMovement::Swapped {
+                        a: target_pos,
+                        b: rand_tile_pos,
+                    });
                     target_pos = rand_tile_pos;
                 }
                 None => {
@@ -622,71 +918,456 @@ impl Board {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::game::being::{BeingType, ENEMY_START_DMG};
+    use crate::game::being::BeingType;
+    use crate::game::config::BeingStartStats;
+    use crate::game::randomizer::rng_from_seed;
     use crate::game::{DEFAULT_BOARD_HEIGHT, DEFAULT_BOARD_WIDTH};
 
     fn testhelp_custom_random_board(
         w: usize,
         h: usize,
         enemy: &Being,
-        boss: &Being,
-        randomizer_tiles: &Vec<TileType>,
+        special_generator: &mut SpecialGenerator,
+        rng: &mut GameRng,
+        randomizer_tiles: &[TileType],
     ) -> Board {
         let mut r = WeightedRandomizer::default();
         for tile_type in randomizer_tiles.iter() {
-            r.add_to_weight(*tile_type as usize, 1);
+            r.set_weight(*tile_type as usize, 1);
         }
 
         // create the board
 
         let mut b = Board {
+            w,
+            h,
             tiles: vec![],
             tile_randomizer: r,
+            num_specials: 0,
+            min_destruction_selection: GameConfig::default().min_destruction_selection(),
+            tile_weights: TileWeights::default(),
             selection_start: None,
+            unused_tile_id: 1,
+            movements: vec![],
         };
 
         // tiles
 
-        for _ in 0..w {
+        for _ in 0..h {
             let new_idx = b.tiles.len();
             b.tiles.push(vec![]);
-            for _ in 0..h {
+            for _ in 0..w {
                 b.tiles[new_idx].push(Tile::default());
             }
         }
-        b.apply_gravity_and_randomize_new_tiles(enemy, boss);
+        b.apply_gravity_and_randomize_new_tiles(enemy, special_generator, rng);
 
         b
     }
 
+    fn testhelp_tile_types(b: &Board) -> Vec<TileType> {
+        b.tiles
+            .iter()
+            .flat_map(|row| row.iter().map(|tile| tile.tile_type))
+            .collect()
+    }
+
     #[test]
     fn test_incoming_damage() {
-        let enemy = Being::new(BeingType::Enemy);
-        let boss = Being::new(BeingType::Special);
+        let enemy = Being::new(
+            BeingType::Enemy,
+            &BeingStartStats::from(BeingType::Enemy),
+            1,
+            1,
+        );
+        let mut special_generator = SpecialGenerator::default();
+        let mut rng = rng_from_seed(0);
         let mut b = testhelp_custom_random_board(
             DEFAULT_BOARD_WIDTH,
             DEFAULT_BOARD_HEIGHT,
             &enemy,
-            &boss,
-            &vec![TileType::Enemy],
+            &mut special_generator,
+            &mut rng,
+            &[TileType::Enemy],
         );
 
         assert_eq!(
             b.incoming_damage(),
-            ENEMY_START_DMG * DEFAULT_BOARD_WIDTH as isize * DEFAULT_BOARD_HEIGHT as isize
+            enemy.base_output_damage * DEFAULT_BOARD_WIDTH * DEFAULT_BOARD_HEIGHT
         );
 
         let mut tp;
         for _ in 0..1000 {
             tp = TilePosition::new(0, 0);
             for _ in 0..3 {
-                b.select_tile(tp);
-                tp = tp + TilePosition::try_from(Wind8::R).expect("");
+                b.select_tile(&tp).expect("");
+                tp = tp + TilePosition::from(Wind8::R);
             }
             assert_eq!(
                 b.incoming_damage(),
-                ENEMY_START_DMG * DEFAULT_BOARD_WIDTH as isize * DEFAULT_BOARD_HEIGHT as isize
+                enemy.base_output_damage * DEFAULT_BOARD_WIDTH * DEFAULT_BOARD_HEIGHT
             );
         }
     }
+
+    #[test]
+    fn test_same_seed_same_board() {
+        let enemy = Being::new(
+            BeingType::Enemy,
+            &BeingStartStats::from(BeingType::Enemy),
+            1,
+            1,
+        );
+        let boards = |seed: u64| {
+            let mut rng = rng_from_seed(seed);
+            let mut special_generator = SpecialGenerator::default();
+            let mut b = Board::new(
+                &GameConfig::default(),
+                &enemy,
+                &mut special_generator,
+                &mut rng,
+            );
+            let fresh = testhelp_tile_types(&b);
+            b.scramble(&mut rng);
+            (fresh, testhelp_tile_types(&b))
+        };
+        assert!(boards(42) == boards(42));
+    }
+
+    #[test]
+    fn test_non_square_boards() {
+        let enemy = Being::new(
+            BeingType::Enemy,
+            &BeingStartStats::from(BeingType::Enemy),
+            1,
+            1,
+        );
+        for (w, h) in [(5, 8), (8, 6), (2, 1)] {
+            let config = GameConfig::builder()
+                .board_size(w, h)
+                .min_destruction_selection(2)
+                .build()
+                .expect("");
+            let mut rng = rng_from_seed(7);
+            let mut special_generator = SpecialGenerator::default();
+            let mut b = Board::new(&config, &enemy, &mut special_generator, &mut rng);
+            assert_eq!((b.width(), b.height()), (w, h));
+            assert_eq!(b.tiles.len(), h);
+            assert!(b.tiles.iter().all(|row| row.len() == w));
+            let (w, h) = (w as isize, h as isize);
+            assert!(b.get_tile(&TilePosition::new(h - 1, w - 1)).is_some());
+            assert!(b.get_tile(&TilePosition::new(h, 0)).is_none());
+            assert!(b.get_tile(&TilePosition::new(0, w)).is_none());
+
+            // specials are found where they are
+            for (tp, _, id) in b.specials(&[]) {
+                match b.get_tile(&tp).expect("").tile_info {
+                    TileInfo::Special(special) => assert_eq!(special.id, id),
+                    _ => panic!("specials gave a position that isn't a special"),
+                }
+            }
+
+            // gravity refills the far corner
+            b.destroy_3x3_centered_at(
+                &TilePosition::new(h - 1, w - 1),
+                &enemy,
+                &mut special_generator,
+                &mut rng,
+            );
+            assert!(!testhelp_tile_types(&b).contains(&TileType::None));
+
+            // scramble and swaps only move tiles around
+            let mut before = testhelp_tile_types(&b);
+            b.scramble(&mut rng);
+            b.swap_position_with_random_other(&TilePosition::new(h - 1, w - 1), &mut rng);
+            let mut after = testhelp_tile_types(&b);
+            before.sort_by_key(|tt| *tt as usize);
+            after.sort_by_key(|tt| *tt as usize);
+            assert!(before == after);
+            for _ in 0..10 {
+                let tp = b
+                    .random_tile_of_type(TileType::Enemy, &mut rng)
+                    .or_else(|| b.random_tile_of_type(TileType::Special, &mut rng));
+                if let Some(tp) = tp {
+                    assert!(b.position_valid(&tp));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_square_selection() {
+        let enemy = Being::new(
+            BeingType::Enemy,
+            &BeingStartStats::from(BeingType::Enemy),
+            1,
+            1,
+        );
+        let mut special_generator = SpecialGenerator::default();
+        let mut rng = rng_from_seed(0);
+        let (w, h) = (5, 8);
+        let mut b = testhelp_custom_random_board(
+            w,
+            h,
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+            &[TileType::Sword],
+        );
+        let (w, h) = (w as isize, h as isize);
+        // down the last column and then along the bottom row
+        for y in 0..h {
+            b.select_tile(&TilePosition::new(y, w - 1)).expect("");
+        }
+        for x in (0..(w - 1)).rev() {
+            b.select_tile(&TilePosition::new(h - 1, x)).expect("");
+        }
+        assert_eq!(
+            b.select_tile(&TilePosition::new(h, 0)),
+            Err(SelectionError::OutOfBounds)
+        );
+        assert_eq!(
+            b.select_tile(&TilePosition::new(0, w)),
+            Err(SelectionError::OutOfBounds)
+        );
+        let (hit, selection_hits) = b.drop_selection(&Player::default(), 1);
+        assert!(hit);
+        assert_eq!(selection_hits.len(), (w + h - 1) as usize);
+        assert!(selection_hits.iter().all(|sh| sh.destroyed));
+        b.apply_gravity_and_randomize_new_tiles(&enemy, &mut special_generator, &mut rng);
+        assert!(!testhelp_tile_types(&b).contains(&TileType::None));
+    }
+
+    #[test]
+    fn test_movements_replay_board_changes() {
+        let enemy = Being::new(
+            BeingType::Enemy,
+            &BeingStartStats::from(BeingType::Enemy),
+            1,
+            1,
+        );
+        let mut special_generator = SpecialGenerator::default();
+        let mut rng = rng_from_seed(3);
+        let mut b = Board::new(
+            &GameConfig::default(),
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+        );
+        assert!(b.take_movements().is_empty());
+
+        let w = b.width();
+        let idx = |tp: &TilePosition| tp.y as usize * w + tp.x as usize;
+        let mut replayed = testhelp_tile_types(&b);
+        let corner = TilePosition::new(b.height() as isize - 1, w as isize - 1);
+        b.destroy_3x3_centered_at(&corner, &enemy, &mut special_generator, &mut rng);
+        b.scramble(&mut rng);
+        b.swap_position_with_random_other(&corner, &mut rng);
+        b.replace_tiles(
+            TileType::Coin,
+            TileType::Potion,
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+        );
+        b.reroll(&enemy, &mut special_generator, &mut rng);
+
+        let movements = b.take_movements();
+        assert!(movements
+            .iter()
+            .any(|m| matches!(m, TileMovement::Fell { .. })));
+        for movement in movements.iter() {
+            match movement {
+                TileMovement::Fell { from, to } => {
+                    replayed[idx(to)] = replayed[idx(from)];
+                    replayed[idx(from)] = TileType::None;
+                }
+                TileMovement::Spawned { at, tile } => replayed[idx(at)] = tile.tile_type,
+                TileMovement::Swapped { a, b } => replayed.swap(idx(a), idx(b)),
+                TileMovement::Destroyed { at, tile } => {
+                    assert!(replayed[idx(at)] == tile.tile_type);
+                    replayed[idx(at)] = TileType::None;
+                }
+                TileMovement::Replaced { at, old, new } => {
+                    assert!(replayed[idx(at)] == old.tile_type);
+                    replayed[idx(at)] = new.tile_type;
+                }
+            }
+        }
+        assert!(replayed == testhelp_tile_types(&b));
+        assert!(b.take_movements().is_empty());
+
+        #[cfg(feature = "json")]
+        {
+            let json = serde_json::to_string(&movements).expect("");
+            let parsed: Vec<TileMovement> = serde_json::from_str(&json).expect("");
+            assert_eq!(parsed, movements);
+        }
+    }
+
+    #[test]
+    fn test_tile_ids_follow_tiles() {
+        let enemy = Being::new(
+            BeingType::Enemy,
+            &BeingStartStats::from(BeingType::Enemy),
+            1,
+            1,
+        );
+        let mut special_generator = SpecialGenerator::default();
+        let mut rng = rng_from_seed(5);
+        let mut b = Board::new(
+            &GameConfig::default(),
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+        );
+        let ids = |b: &Board| {
+            let mut ids: Vec<TileId> = b.tiles.iter().flatten().map(|tile| tile.id).collect();
+            ids.sort();
+            ids
+        };
+        let mut before = ids(&b);
+        before.dedup();
+        assert_eq!(before.len(), b.num_tiles());
+        assert!(!before.contains(&0));
+
+        b.scramble(&mut rng);
+        b.swap_position_with_random_other(&TilePosition::new(0, 0), &mut rng);
+        assert!(ids(&b) == before);
+        for (y, row) in b.tiles.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                assert!(
+                    b.tile_position(tile.id) == Some(TilePosition::new(y as isize, x as isize))
+                );
+            }
+        }
+
+        let destroyed = b.destroy_3x3_centered_at(
+            &TilePosition::new(2, 2),
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+        );
+        let newest = *before.last().expect("");
+        for tile in destroyed.iter() {
+            assert!(b.tile_position(tile.id).is_none());
+        }
+        let after = ids(&b);
+        assert_eq!(after.iter().filter(|id| **id > newest).count(), 9);
+        assert!(b.tile_position(0).is_none());
+    }
+
+    #[test]
+    fn test_gap_fill() {
+        let enemy = Being::new(
+            BeingType::Enemy,
+            &BeingStartStats::from(BeingType::Enemy),
+            1,
+            1,
+        );
+        let mut special_generator = SpecialGenerator::default();
+        let mut rng = rng_from_seed(0);
+        let mut b = testhelp_custom_random_board(
+            5,
+            8,
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+            &[TileType::Sword],
+        );
+        let select_filling_gaps = |b: &mut Board, y, x| {
+            let gap = b.gap_fill(&TilePosition::new(y, x))?;
+            for p in gap.iter() {
+                b.select_tile(p).expect("");
+            }
+            Ok(gap.len())
+        };
+        assert_eq!(select_filling_gaps(&mut b, 0, 2), Ok(1));
+        assert_eq!(select_filling_gaps(&mut b, 4, 2), Ok(4));
+        assert_eq!(select_filling_gaps(&mut b, 6, 4), Ok(2));
+        assert_eq!(select_filling_gaps(&mut b, 6, 0), Ok(4));
+        assert_eq!(b.selection().len(), 11);
+        assert_eq!(
+            select_filling_gaps(&mut b, 5, 2),
+            Err(SelectionError::NotInLine)
+        );
+        assert_eq!(
+            select_filling_gaps(&mut b, 3, 3),
+            Err(SelectionError::RepeatedTile)
+        );
+        assert_eq!(
+            select_filling_gaps(&mut b, 8, 0),
+            Err(SelectionError::OutOfBounds)
+        );
+
+        b.replace_tile(
+            &TilePosition::new(7, 1),
+            TileType::Potion,
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+        );
+        assert_eq!(b.selection().len(), 11);
+        assert_eq!(
+            select_filling_gaps(&mut b, 7, 2),
+            Err(SelectionError::NotInLine)
+        );
+        b.select_tile(&TilePosition::new(7, 0)).expect("");
+        assert_eq!(
+            select_filling_gaps(&mut b, 7, 2),
+            Err(SelectionError::TypesDontConnect)
+        );
+
+        // going back onto the selection cuts it like select_tile does
+        assert_eq!(select_filling_gaps(&mut b, 4, 2), Ok(1));
+        assert_eq!(b.selection().len(), 5);
+    }
+
+    #[test]
+    fn test_legal_path_limits() {
+        let enemy = Being::new(
+            BeingType::Enemy,
+            &BeingStartStats::from(BeingType::Enemy),
+            1,
+            1,
+        );
+        let mut special_generator = SpecialGenerator::default();
+        let mut rng = rng_from_seed(0);
+        // every tile is adjacent to every other tile, so every ordering is a path
+        let b = testhelp_custom_random_board(
+            2,
+            2,
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+            &[TileType::Sword],
+        );
+        let num_paths = |max_length, max_count| {
+            b.legal_paths(PathLimits {
+                max_length,
+                max_count,
+            })
+            .count()
+        };
+        assert_eq!(num_paths(None, None), 4 * 3 * 2 + 4 * 3 * 2);
+        assert_eq!(num_paths(Some(3), None), 4 * 3 * 2);
+        assert_eq!(num_paths(Some(2), None), 0);
+        assert_eq!(num_paths(None, Some(5)), 5);
+        assert!(b.has_legal_path());
+
+        // no tile connects with more than one other
+        let mut b = testhelp_custom_random_board(
+            2,
+            2,
+            &enemy,
+            &mut special_generator,
+            &mut rng,
+            &[TileType::Sword],
+        );
+        b.tiles[0][0].tile_type = TileType::Potion;
+        b.tiles[0][1].tile_type = TileType::Coin;
+        b.tiles[1][0].tile_type = TileType::Shield;
+        assert_eq!(b.legal_paths(PathLimits::default()).count(), 0);
+        assert!(!b.has_legal_path());
+    }
 }
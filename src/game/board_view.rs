@@ -0,0 +1,89 @@
+use crate::game::board::Board;
+use crate::game::tile::{Tile, TileId, TilePosition};
+
+// everything a frontend needs to draw the board, borrowed in one go
+#[derive(Copy, Clone)]
+pub struct BoardView<'a> {
+    board: &'a Board,
+}
+
+impl<'a> BoardView<'a> {
+    pub fn new(board: &'a Board) -> Self {
+        Self { board }
+    }
+
+    pub fn width(&self) -> usize {
+        self.board.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.board.height()
+    }
+
+    pub fn num_tiles(&self) -> usize {
+        self.board.num_tiles()
+    }
+
+    pub fn tile(&self, tile_position: &TilePosition) -> Option<&'a Tile> {
+        self.board.tile_ref(tile_position)
+    }
+
+    // where the tile with this id is now, None once it's been destroyed
+    pub fn tile_position(&self, id: TileId) -> Option<TilePosition> {
+        self.board.tile_position(id)
+    }
+
+    // top to bottom, each row left to right
+    pub fn rows(&self) -> impl Iterator<Item = &'a [Tile]> {
+        self.board.rows()
+    }
+
+    // row major, same order as rows
+    pub fn cells(&self) -> impl Iterator<Item = (TilePosition, &'a Tile)> {
+        self.board.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, tile)| (TilePosition::new(y as isize, x as isize), tile))
+        })
+    }
+
+    // in the order the tiles were selected, empty if nothing is selected
+    pub fn selection(&self) -> Vec<TilePosition> {
+        self.board.selection()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::config::GameConfig;
+    use crate::game::tile::TilePosition;
+    use crate::game::Game;
+
+    #[test]
+    fn test_board_view() {
+        let mut game = Game::new(
+            GameConfig::builder()
+                .seed(4)
+                .board_size(4, 7)
+                .build()
+                .expect(""),
+        );
+        let line = crate::game::tests::testhelp_connected_lines(&game)[0];
+        for tile_position in line.iter() {
+            game.select_tile(tile_position).expect("");
+        }
+
+        let view = game.board_view();
+        assert_eq!((view.width(), view.height(), view.num_tiles()), (4, 7, 28));
+        assert_eq!(view.rows().count(), 7);
+        assert!(view.rows().all(|row| row.len() == 4));
+        assert_eq!(view.cells().count(), 28);
+        for (tile_position, tile) in view.cells() {
+            let from_game = game.get_tile(&tile_position).expect("");
+            assert!(tile.tile_type == from_game.tile_type);
+            assert!(std::ptr::eq(view.tile(&tile_position).expect(""), tile));
+        }
+        assert!(view.tile(&TilePosition::new(7, 0)).is_none());
+        assert!(view.selection() == line);
+    }
+}
@@ -1,7 +1,10 @@
 use crate::game::improvement_choices::ImprovementChoiceDisplay;
-use crate::game::randomizer::{WeightedRandomizer, WeightedRandomizerType};
+use crate::game::randomizer::{GameRng, WeightedRandomizer, WeightedRandomizerType};
+use crate::game::ruleset::Ruleset;
 use crate::game::stat_modifiers::{DefenseIncrease, WeaponDamageIncrease};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub enum CoinPurchaseType {
     Defense,
     Attack,
@@ -19,12 +22,16 @@ impl TryFrom<usize> for CoinPurchaseType {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub enum CoinPurchaseInfo {
     Defense(DefenseIncrease),
     Attack(WeaponDamageIncrease),
 }
 
-#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Hash)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum CoinPurchasePieceType {
     Helmet,
     Breastplate,
@@ -64,25 +71,29 @@ impl TryFrom<CoinPurchasePieceType> for CoinPurchaseType {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct CoinPurchaseGenerator {
     piece_type_randomizer: WeightedRandomizer,
     defense_increase_randomizer: WeightedRandomizer,
     attack_increase_randomizer: WeightedRandomizer,
 }
 
-impl Default for CoinPurchaseGenerator {
-    fn default() -> Self {
+impl CoinPurchaseGenerator {
+    pub fn new(ruleset: &Ruleset) -> Self {
         let mut piece_type_randomizer =
             WeightedRandomizer::new(WeightedRandomizerType::MetaSubAllOnObtain);
         for pt in 0..(CoinPurchasePieceType::COUNT as usize) {
             piece_type_randomizer.set_weight(pt, 1);
         }
         let mut defense_increase_randomizer = WeightedRandomizer::default();
-        defense_increase_randomizer.set_weight(1, 99);
-        defense_increase_randomizer.set_weight(2, 1);
+        for iw in ruleset.defense_purchase_weights.iter() {
+            defense_increase_randomizer.set_weight(iw.increase, iw.weight);
+        }
         let mut attack_increase_randomizer = WeightedRandomizer::default();
-        attack_increase_randomizer.set_weight(1, 99);
-        attack_increase_randomizer.set_weight(2, 1);
+        for iw in ruleset.attack_purchase_weights.iter() {
+            attack_increase_randomizer.set_weight(iw.increase, iw.weight);
+        }
         Self {
             piece_type_randomizer,
             defense_increase_randomizer,
@@ -91,6 +102,8 @@ impl Default for CoinPurchaseGenerator {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct CoinPurchase {
     pub coin_purchase_type: CoinPurchaseType,
     pub coin_purchase_info: CoinPurchaseInfo,
@@ -122,20 +135,22 @@ impl From<&CoinPurchase> for ImprovementChoiceDisplay {
 }
 
 impl CoinPurchaseGenerator {
-    pub fn get(&mut self) -> CoinPurchase {
+    pub fn get(&mut self, rng: &mut GameRng) -> CoinPurchase {
         let coin_purchase_piece_type = CoinPurchasePieceType::try_from(
-            self.piece_type_randomizer.weighted_random().expect(""),
+            self.piece_type_randomizer.weighted_random(rng).expect(""),
         )
         .expect("");
         let coin_purchase_type = CoinPurchaseType::try_from(coin_purchase_piece_type).expect("");
         let coin_purchase_info = match coin_purchase_type {
             CoinPurchaseType::Defense => CoinPurchaseInfo::Defense(
                 self.defense_increase_randomizer
-                    .weighted_random()
+                    .weighted_random(rng)
                     .expect(""),
             ),
             CoinPurchaseType::Attack => CoinPurchaseInfo::Attack(
-                self.attack_increase_randomizer.weighted_random().expect(""),
+                self.attack_increase_randomizer
+                    .weighted_random(rng)
+                    .expect(""),
             ),
         };
         CoinPurchase {
@@ -1,3 +1,5 @@
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct CollectionMultipliers {
     pub shield_collection_multiplier: usize,
     pub coin_collection_multiplier: usize,
@@ -0,0 +1,353 @@
+use crate::game::being::{BeingType, MIN_BASE_DAMAGE};
+use crate::game::error::ConfigError;
+use crate::game::randomizer::Weight;
+use crate::game::ruleset::Ruleset;
+use crate::game::tile::TileType;
+use crate::game::{ABILITY_SLOTS, DEFAULT_BOARD_HEIGHT, DEFAULT_BOARD_WIDTH};
+use std::sync::Arc;
+
+const DEFAULT_MIN_DESTRUCTION_SELECTION: usize = 3;
+const DEFAULT_INITIAL_MIN_TURNS_BETWEEN_SPECIALS: usize = 2;
+const DEFAULT_UNDO_LIMIT: usize = 0;
+
+// stats a being starts with before any scaling is applied
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BeingStartStats {
+    pub base_output_damage: usize,
+    pub weapon_output_damage: usize,
+    pub hit_points: usize,
+    pub shields: usize,
+}
+
+// how likely each tile type is to drop in. specials are left out because their
+// weight is driven by Board::activate_special_spawns
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TileWeights {
+    pub potion: Weight,
+    pub shield: Weight,
+    pub coin: Weight,
+    pub sword: Weight,
+    pub enemy: Weight,
+}
+
+impl Default for TileWeights {
+    fn default() -> Self {
+        Self {
+            potion: 100,
+            shield: 100,
+            coin: 100,
+            sword: 80,
+            enemy: 60,
+        }
+    }
+}
+
+impl TileWeights {
+    pub fn weight(&self, tile_type: TileType) -> Weight {
+        match tile_type {
+            TileType::Potion => self.potion,
+            TileType::Shield => self.shield,
+            TileType::Coin => self.coin,
+            TileType::Sword => self.sword,
+            TileType::Enemy => self.enemy,
+            TileType::Special | TileType::COUNT | TileType::None => 0,
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GameConfig {
+    seed: Option<u64>,
+    board_width: usize,
+    board_height: usize,
+    ability_slots: usize,
+    min_destruction_selection: usize,
+    initial_min_turns_between_specials: usize,
+    tile_weights: TileWeights,
+    player_start_stats: BeingStartStats,
+    enemy_start_stats: BeingStartStats,
+    special_start_stats: BeingStartStats,
+    // shared with the special generator, so cloning a game doesn't copy it
+    ruleset: Arc<Ruleset>,
+    undo_limit: usize,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            board_width: DEFAULT_BOARD_WIDTH,
+            board_height: DEFAULT_BOARD_HEIGHT,
+            ability_slots: ABILITY_SLOTS,
+            min_destruction_selection: DEFAULT_MIN_DESTRUCTION_SELECTION,
+            initial_min_turns_between_specials: DEFAULT_INITIAL_MIN_TURNS_BETWEEN_SPECIALS,
+            tile_weights: TileWeights::default(),
+            player_start_stats: BeingStartStats::from(BeingType::Player),
+            enemy_start_stats: BeingStartStats::from(BeingType::Enemy),
+            special_start_stats: BeingStartStats::from(BeingType::Special),
+            ruleset: Arc::new(Ruleset::default()),
+            undo_limit: DEFAULT_UNDO_LIMIT,
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn builder() -> GameConfigBuilder {
+        GameConfigBuilder::default()
+    }
+
+    // None means a random seed gets picked when the Game is created
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn board_width(&self) -> usize {
+        self.board_width
+    }
+
+    pub fn board_height(&self) -> usize {
+        self.board_height
+    }
+
+    pub fn ability_slots(&self) -> usize {
+        self.ability_slots
+    }
+
+    pub fn min_destruction_selection(&self) -> usize {
+        self.min_destruction_selection
+    }
+
+    pub fn initial_min_turns_between_specials(&self) -> usize {
+        self.initial_min_turns_between_specials
+    }
+
+    pub fn tile_weights(&self) -> &TileWeights {
+        &self.tile_weights
+    }
+
+    pub fn player_start_stats(&self) -> &BeingStartStats {
+        &self.player_start_stats
+    }
+
+    pub fn enemy_start_stats(&self) -> &BeingStartStats {
+        &self.enemy_start_stats
+    }
+
+    pub fn special_start_stats(&self) -> &BeingStartStats {
+        &self.special_start_stats
+    }
+
+    pub fn ruleset(&self) -> &Ruleset {
+        &self.ruleset
+    }
+
+    pub(crate) fn shared_ruleset(&self) -> Arc<Ruleset> {
+        Arc::clone(&self.ruleset)
+    }
+
+    // how many turns back Game::undo can go, 0 means undo is turned off
+    pub fn undo_limit(&self) -> usize {
+        self.undo_limit
+    }
+
+    pub(crate) fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+    }
+}
+
+#[derive(Default)]
+pub struct GameConfigBuilder {
+    config: GameConfig,
+}
+
+impl GameConfigBuilder {
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn board_size(mut self, width: usize, height: usize) -> Self {
+        self.config.board_width = width;
+        self.config.board_height = height;
+        self
+    }
+
+    pub fn ability_slots(mut self, ability_slots: usize) -> Self {
+        self.config.ability_slots = ability_slots;
+        self
+    }
+
+    pub fn min_destruction_selection(mut self, min_destruction_selection: usize) -> Self {
+        self.config.min_destruction_selection = min_destruction_selection;
+        self
+    }
+
+    pub fn initial_min_turns_between_specials(mut self, turns: usize) -> Self {
+        self.config.initial_min_turns_between_specials = turns;
+        self
+    }
+
+    pub fn tile_weights(mut self, tile_weights: TileWeights) -> Self {
+        self.config.tile_weights = tile_weights;
+        self
+    }
+
+    pub fn player_start_stats(mut self, start_stats: BeingStartStats) -> Self {
+        self.config.player_start_stats = start_stats;
+        self
+    }
+
+    pub fn enemy_start_stats(mut self, start_stats: BeingStartStats) -> Self {
+        self.config.enemy_start_stats = start_stats;
+        self
+    }
+
+    pub fn special_start_stats(mut self, start_stats: BeingStartStats) -> Self {
+        self.config.special_start_stats = start_stats;
+        self
+    }
+
+    pub fn ruleset(mut self, ruleset: Ruleset) -> Self {
+        self.config.ruleset = Arc::new(ruleset);
+        self
+    }
+
+    // off (0) by default. with undo on, every submitted turn clones the whole game
+    // first, and up to undo_limit of those clones are kept around
+    pub fn undo_limit(mut self, undo_limit: usize) -> Self {
+        self.config.undo_limit = undo_limit;
+        self
+    }
+
+    pub fn build(self) -> Result<GameConfig, ConfigError> {
+        let config = self.config;
+        let num_tiles = config.board_width * config.board_height;
+        // at least 2 tiles are needed for a Chaotic special to have somewhere to go
+        if num_tiles < 2 {
+            return Err(ConfigError::BoardTooSmall {
+                width: config.board_width,
+                height: config.board_height,
+            });
+        }
+        if config.min_destruction_selection == 0 || config.min_destruction_selection > num_tiles {
+            return Err(ConfigError::InvalidMinDestructionSelection {
+                min_destruction_selection: config.min_destruction_selection,
+                num_tiles,
+            });
+        }
+        let weights = &config.tile_weights;
+        // specials are weighted up to the enemy weight once they start spawning
+        let total_weight = [
+            weights.shield,
+            weights.coin,
+            weights.sword,
+            weights.enemy,
+            weights.enemy,
+        ]
+        .iter()
+        .try_fold(weights.potion, |total, weight| total.checked_add(*weight))
+        .ok_or(ConfigError::TileWeightsOverflow)?;
+        if total_weight == 0 {
+            return Err(ConfigError::NoTileWeights);
+        }
+        if config.player_start_stats.hit_points == 0 {
+            return Err(ConfigError::PlayerStartsDead);
+        }
+        // blunting never takes an attacker below MIN_BASE_DAMAGE
+        for (being_type, start_stats) in [
+            (BeingType::Enemy, &config.enemy_start_stats),
+            (BeingType::Special, &config.special_start_stats),
+        ] {
+            if start_stats.base_output_damage < MIN_BASE_DAMAGE {
+                return Err(ConfigError::BaseDamageTooLow {
+                    being_type,
+                    base_output_damage: start_stats.base_output_damage,
+                    min: MIN_BASE_DAMAGE,
+                });
+            }
+        }
+        config.ruleset.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::error::RulesetError;
+
+    #[test]
+    fn test_builder_validation() {
+        assert!(GameConfig::builder().build() == Ok(GameConfig::default()));
+        assert_eq!(
+            GameConfig::builder().board_size(1, 1).build(),
+            Err(ConfigError::BoardTooSmall {
+                width: 1,
+                height: 1
+            })
+        );
+        assert_eq!(
+            GameConfig::builder().min_destruction_selection(0).build(),
+            Err(ConfigError::InvalidMinDestructionSelection {
+                min_destruction_selection: 0,
+                num_tiles: DEFAULT_BOARD_WIDTH * DEFAULT_BOARD_HEIGHT,
+            })
+        );
+        assert_eq!(
+            GameConfig::builder()
+                .tile_weights(TileWeights {
+                    potion: 0,
+                    shield: 0,
+                    coin: 0,
+                    sword: 0,
+                    enemy: 0,
+                })
+                .build(),
+            Err(ConfigError::NoTileWeights)
+        );
+        assert_eq!(
+            GameConfig::builder()
+                .player_start_stats(BeingStartStats {
+                    base_output_damage: 1,
+                    weapon_output_damage: 1,
+                    hit_points: 0,
+                    shields: 0,
+                })
+                .build(),
+            Err(ConfigError::PlayerStartsDead)
+        );
+        assert_eq!(
+            GameConfig::builder()
+                .tile_weights(TileWeights {
+                    potion: usize::MAX,
+                    ..TileWeights::default()
+                })
+                .build(),
+            Err(ConfigError::TileWeightsOverflow)
+        );
+        assert_eq!(
+            GameConfig::builder()
+                .enemy_start_stats(BeingStartStats {
+                    base_output_damage: 0,
+                    ..BeingStartStats::from(BeingType::Enemy)
+                })
+                .build(),
+            Err(ConfigError::BaseDamageTooLow {
+                being_type: BeingType::Enemy,
+                base_output_damage: 0,
+                min: MIN_BASE_DAMAGE,
+            })
+        );
+        let mut ruleset = Ruleset::default();
+        ruleset.special_scaling.boss.denominator = 0;
+        assert_eq!(
+            GameConfig::builder().ruleset(ruleset).build(),
+            Err(ConfigError::InvalidRuleset(RulesetError::ZeroDenominator(
+                "special_scaling.boss"
+            )))
+        );
+    }
+}
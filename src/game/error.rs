@@ -0,0 +1,274 @@
+use crate::game::abilities::AbilityCooldown;
+use crate::game::being::BeingType;
+use crate::game::turn::WrongPhase;
+use std::fmt;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SelectionError {
+    OutOfBounds,
+    NotAdjacent,
+    TypesDontConnect,
+    NothingSelected,
+    RepeatedTile,
+    NotInLine,
+    PathTooShort { length: usize, min_length: usize },
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "that position is not on the board"),
+            Self::NotAdjacent => write!(f, "that tile is not next to the end of the selection"),
+            Self::TypesDontConnect => write!(f, "that tile does not connect with the selection"),
+            Self::NothingSelected => write!(f, "there is no selection"),
+            Self::RepeatedTile => write!(f, "that tile is already in the path"),
+            Self::NotInLine => write!(
+                f,
+                "that tile is not in a straight or diagonal line from the end of the selection"
+            ),
+            Self::PathTooShort { length, min_length } => write!(
+                f,
+                "a path of {} tiles is too short to hit (at least {} are needed)",
+                length, min_length
+            ),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AbilityError {
+    SlotOutOfRange { slot: usize, num_slots: usize },
+    EmptySlot,
+    OnCooldown { remaining: AbilityCooldown },
+}
+
+impl fmt::Display for AbilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SlotOutOfRange { slot, num_slots } => write!(
+                f,
+                "ability slot {} does not exist (there are {})",
+                slot, num_slots
+            ),
+            Self::EmptySlot => write!(f, "there is no ability in that slot"),
+            Self::OnCooldown { remaining } => {
+                write!(f, "ability is on cooldown for {} more turns", remaining)
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ImprovementError {
+    WrongCount { expected: usize, given: usize },
+    IndexOutOfRange { index: usize, num_choices: usize },
+    DuplicateIndex(usize),
+}
+
+impl fmt::Display for ImprovementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongCount { expected, given } => write!(
+                f,
+                "{} improvements must be chosen but {} were given",
+                expected, given
+            ),
+            Self::IndexOutOfRange { index, num_choices } => write!(
+                f,
+                "improvement {} does not exist (there are {})",
+                index, num_choices
+            ),
+            Self::DuplicateIndex(index) => {
+                write!(f, "improvement {} was chosen more than once", index)
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum UndoError {
+    Disabled,
+    NothingToUndo,
+    NothingToRedo,
+}
+
+impl fmt::Display for UndoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "undo is turned off for this game"),
+            Self::NothingToUndo => write!(f, "there is no turn to undo"),
+            Self::NothingToRedo => write!(f, "there is no undone turn to redo"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameError {
+    WrongPhase(WrongPhase),
+    Selection(SelectionError),
+    Ability(AbilityError),
+    Improvement(ImprovementError),
+    Undo(UndoError),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongPhase(e) => e.fmt(f),
+            Self::Selection(e) => e.fmt(f),
+            Self::Ability(e) => e.fmt(f),
+            Self::Improvement(e) => e.fmt(f),
+            Self::Undo(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SelectionError {}
+impl std::error::Error for AbilityError {}
+impl std::error::Error for ImprovementError {}
+impl std::error::Error for UndoError {}
+impl std::error::Error for GameError {}
+
+impl From<WrongPhase> for GameError {
+    fn from(value: WrongPhase) -> Self {
+        Self::WrongPhase(value)
+    }
+}
+
+impl From<SelectionError> for GameError {
+    fn from(value: SelectionError) -> Self {
+        Self::Selection(value)
+    }
+}
+
+impl From<AbilityError> for GameError {
+    fn from(value: AbilityError) -> Self {
+        Self::Ability(value)
+    }
+}
+
+impl From<ImprovementError> for GameError {
+    fn from(value: ImprovementError) -> Self {
+        Self::Improvement(value)
+    }
+}
+
+impl From<UndoError> for GameError {
+    fn from(value: UndoError) -> Self {
+        Self::Undo(value)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ConfigError {
+    BoardTooSmall {
+        width: usize,
+        height: usize,
+    },
+    InvalidMinDestructionSelection {
+        min_destruction_selection: usize,
+        num_tiles: usize,
+    },
+    NoTileWeights,
+    TileWeightsOverflow,
+    PlayerStartsDead,
+    BaseDamageTooLow {
+        being_type: BeingType,
+        base_output_damage: usize,
+        min: usize,
+    },
+    InvalidRuleset(RulesetError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BoardTooSmall { width, height } => write!(
+                f,
+                "a {}x{} board is too small, it needs at least 2 tiles",
+                width, height
+            ),
+            Self::InvalidMinDestructionSelection {
+                min_destruction_selection,
+                num_tiles,
+            } => write!(
+                f,
+                "minimum selection of {} must be between 1 and the {} tiles on the board",
+                min_destruction_selection, num_tiles
+            ),
+            Self::NoTileWeights => write!(f, "at least one tile weight must be above 0"),
+            Self::TileWeightsOverflow => write!(f, "the tile weights are too large to add up"),
+            Self::PlayerStartsDead => write!(f, "the player must start with hit points"),
+            Self::BaseDamageTooLow {
+                being_type,
+                base_output_damage,
+                min,
+            } => write!(
+                f,
+                "{:?} base damage of {} must be at least {}",
+                being_type, base_output_damage, min
+            ),
+            Self::InvalidRuleset(e) => write!(f, "invalid ruleset: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<RulesetError> for ConfigError {
+    fn from(value: RulesetError) -> Self {
+        Self::InvalidRuleset(value)
+    }
+}
+
+// field names are given as their path in a ruleset file, e.g. "special_scaling.boss"
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RulesetError {
+    Parse(String),
+    ZeroDenominator(&'static str),
+    ZeroValue(&'static str),
+    ZeroStartingCents(&'static str),
+    CentsScaleToZero(&'static str),
+    CooldownTooShort {
+        field: &'static str,
+        cooldown: usize,
+        min: usize,
+    },
+    NoPurchaseWeights(&'static str),
+}
+
+impl fmt::Display for RulesetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(msg) => write!(f, "failed to parse ruleset: {}", msg),
+            Self::ZeroDenominator(field) => write!(f, "`{}` has a denominator of 0", field),
+            Self::ZeroValue(field) => write!(f, "`{}` must be above 0", field),
+            Self::ZeroStartingCents(field) => {
+                write!(f, "`{}.starting_cents` must be above 0", field)
+            }
+            Self::CentsScaleToZero(field) => write!(
+                f,
+                "`{}` needs a constant above 0 or a numerator of at least its denominator so costs can't drop to 0",
+                field
+            ),
+            Self::CooldownTooShort {
+                field,
+                cooldown,
+                min,
+            } => write!(
+                f,
+                "`{}` is {} but must be at least {} to survive every level up",
+                field, cooldown, min
+            ),
+            Self::NoPurchaseWeights(field) => {
+                write!(
+                    f,
+                    "`{}` needs at least one entry with a weight above 0",
+                    field
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RulesetError {}
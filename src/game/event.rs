@@ -0,0 +1,71 @@
+use crate::game::abilities::AbilityType;
+use crate::game::improvement_choices::ImprovementType;
+use crate::game::run_summary::CauseOfDeath;
+use crate::game::special::{SpecialIdentifier, SpecialType};
+use crate::game::tile::{TileId, TilePosition, TileType};
+
+// things that happened during a game, in the order they happened, for sound, effects,
+// achievements and the like to react to without comparing game states
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GameEvent {
+    // every tile in a selection that hit, enemies and specials take damage and
+    // everything else is collected
+    TileHit {
+        position: TilePosition,
+        tile_id: TileId,
+        tile_type: TileType,
+        damage: usize,
+        destroyed: bool,
+    },
+    TilesCollected {
+        potions: usize,
+        hit_points_restored: usize,
+        shields: usize,
+        coins: usize,
+        experience_points: usize,
+    },
+    PlayerDamaged {
+        damage: usize,
+        shields_absorbed: usize,
+        hit_points_lost: usize,
+    },
+    BluntingApplied {
+        blunting: usize,
+    },
+    ImprovementQueued(ImprovementType),
+    AbilityCast {
+        slot: usize,
+        ability_type: AbilityType,
+    },
+    SpecialSpawned {
+        position: TilePosition,
+        id: SpecialIdentifier,
+        special_type: SpecialType,
+    },
+    UndeadReanimated {
+        position: TilePosition,
+        id: SpecialIdentifier,
+    },
+    // the enemy at position was turned into a special
+    EnlightenerConverted {
+        enlightener: SpecialIdentifier,
+        position: TilePosition,
+    },
+    KamikazeTicked {
+        position: TilePosition,
+        id: SpecialIdentifier,
+        turns_left: usize,
+    },
+    KamikazeExploded {
+        position: TilePosition,
+        id: SpecialIdentifier,
+    },
+    PlayerDied(CauseOfDeath),
+    // the board was left without any legal paths and got a free reshuffle. the
+    // tile movements say how, and any specials it spawned follow this. if no
+    // reshuffle found a path, PlayerDied(CauseOfDeath::NoLegalPath) comes last
+    BoardReshuffled,
+    // the whole game state jumped back to before the last turn, or forward again
+    TurnUndone,
+    TurnRedone,
+}
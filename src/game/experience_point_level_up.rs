@@ -1,9 +1,9 @@
 use crate::game::abilities::AbilityType;
 use crate::game::abilities::MAX_ABILITY_LEVEL;
 use crate::game::improvement_choices::ImprovementChoiceDisplay;
-use crate::game::randomizer::{WeightedRandomizer, WeightedRandomizerType};
-use crate::game::ABILITY_SLOTS;
+use crate::game::randomizer::{GameRng, WeightedRandomizer, WeightedRandomizerType};
 
+#[allow(dead_code)]
 pub enum ExperiencePointLevelUpType {
     Ability,
     Stat,
@@ -21,11 +21,14 @@ impl TryFrom<usize> for ExperiencePointLevelUpType {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub enum ExperiencePointLevelUpInfo {
     Ability(AbilityType),
     Stat(StatLevelUpInfo),
 }
 
+#[allow(clippy::upper_case_acronyms)]
 pub enum StatLevelUpType {
     MaxHitPoints,
     BaseOutputDamage,
@@ -46,7 +49,8 @@ impl TryFrom<usize> for StatLevelUpType {
     }
 }
 
-#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Hash)]
 pub enum StatLevelUpInfo {
     // TODO: type this stuff
     MaxHitPoints(usize),
@@ -82,16 +86,19 @@ impl StatLevelUpInfo {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct ExperiencePointLevelUpGenerator {
     ability_type_randomizer: WeightedRandomizer,
     chosen_ability_type_randomizer: WeightedRandomizer,
     stat_level_up_type_randomizer: WeightedRandomizer,
     generation: usize,
     chosen_abilities: Vec<AbilityType>,
+    ability_slots: usize,
 }
 
-impl Default for ExperiencePointLevelUpGenerator {
-    fn default() -> Self {
+impl ExperiencePointLevelUpGenerator {
+    pub fn new(ability_slots: usize) -> Self {
         let mut ability_type_randomizer =
             WeightedRandomizer::new(WeightedRandomizerType::MetaSubAllOnObtain);
         for at in 0..(AbilityType::COUNT as usize) {
@@ -109,11 +116,14 @@ impl Default for ExperiencePointLevelUpGenerator {
             ),
             stat_level_up_type_randomizer,
             generation: 0,
-            chosen_abilities: Vec::with_capacity(ABILITY_SLOTS),
+            chosen_abilities: Vec::with_capacity(ability_slots),
+            ability_slots,
         }
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct ExperiencePointLevelUp {
     pub experience_point_level_up_info: ExperiencePointLevelUpInfo,
 }
@@ -143,13 +153,13 @@ impl From<&ExperiencePointLevelUp> for ImprovementChoiceDisplay {
 
 const NUM_ABILITY_OPTIONS: usize = 2;
 impl ExperiencePointLevelUpGenerator {
-    pub fn get(&mut self) -> Option<ExperiencePointLevelUp> {
+    pub fn get(&mut self, rng: &mut GameRng) -> Option<ExperiencePointLevelUp> {
         let xplu_opt = if self.generation < NUM_ABILITY_OPTIONS {
             // first are ability options
-            if ABILITY_SLOTS - self.chosen_abilities.len() > self.generation {
+            if self.ability_slots - self.chosen_abilities.len() > self.generation {
                 // level up potentially unique, potentially existing abilities
                 let atype = AbilityType::try_from(
-                    self.ability_type_randomizer.weighted_random().expect(""),
+                    self.ability_type_randomizer.weighted_random(rng).expect(""),
                 )
                 .expect("");
                 self.chosen_ability_type_randomizer
@@ -160,7 +170,7 @@ impl ExperiencePointLevelUpGenerator {
             } else {
                 // level up existing abilities (since we can't give more
                 // options than there are available ability slots)
-                let value_opt = self.chosen_ability_type_randomizer.weighted_random();
+                let value_opt = self.chosen_ability_type_randomizer.weighted_random(rng);
                 // value_opt could be None since abilities are removed from
                 // chosen_ability_type_randomizer when they hit max level
                 match value_opt {
@@ -179,7 +189,7 @@ impl ExperiencePointLevelUpGenerator {
             // give the rest as stat options
             let slutype = StatLevelUpType::try_from(
                 self.stat_level_up_type_randomizer
-                    .weighted_random()
+                    .weighted_random(rng)
                     .expect(""),
             )
             .expect("");
@@ -5,9 +5,14 @@ use crate::game::coin_purchase::{CoinPurchase, CoinPurchaseGenerator};
 use crate::game::experience_point_level_up::{
     ExperiencePointLevelUp, ExperiencePointLevelUpGenerator,
 };
+use crate::game::randomizer::GameRng;
+use crate::game::ruleset::Ruleset;
 use crate::game::shield_upgrade::{ShieldUpgrade, ShieldUpgradeGenerator};
 
-#[derive(Copy, Clone)]
+pub type ImprovementHeader = &'static str;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum ImprovementType {
     Shields,
     Coins,
@@ -15,7 +20,7 @@ pub enum ImprovementType {
 }
 
 impl ImprovementType {
-    fn header(self) -> &'static str {
+    fn header(self) -> ImprovementHeader {
         match self {
             Self::Shields => "Upgrade a stat",
             Self::Coins => "Purchase an item",
@@ -33,41 +38,86 @@ impl ImprovementType {
 }
 
 // always IMPROVEMENT_CHOICE_SET_SIZE elements in each vector
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub enum ImprovementInfo {
     ShieldUpgradeInfo(Vec<ShieldUpgrade>),
     CoinPurchaseInfo(Vec<CoinPurchase>),
     ExperiencePointLevelUpInfo(Vec<ExperiencePointLevelUp>),
 }
 
-#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct ImprovementChoiceSetGenerator {
     shield_upgrade_generator: ShieldUpgradeGenerator,
     coin_purchase_generator: CoinPurchaseGenerator,
     experience_point_level_up_generator: ExperiencePointLevelUpGenerator,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct ImprovementChoiceDisplay {
     pub description: String,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct ImprovementChoiceSet {
     pub improvement_type: ImprovementType,
-    pub header: &'static str,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_header"))]
+    pub header: ImprovementHeader,
     // always IMPROVEMENT_CHOICE_SET_SIZE elements in this vector
     pub displays: Vec<ImprovementChoiceDisplay>,
     pub info: ImprovementInfo,
     pub num_to_choose: usize,
 }
 
+// headers are always one of the ImprovementType::header strings, so map the saved
+// string back onto the matching &'static str
+#[cfg(feature = "serde")]
+fn deserialize_header<'de, D>(deserializer: D) -> Result<ImprovementHeader, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let header = <String as serde::Deserialize>::deserialize(deserializer)?;
+    for improvement_type in [
+        ImprovementType::Shields,
+        ImprovementType::Coins,
+        ImprovementType::ExperiencePoints,
+    ] {
+        if improvement_type.header() == header {
+            return Ok(improvement_type.header());
+        }
+    }
+    Err(serde::de::Error::custom(format!(
+        "unknown improvement choice set header \"{}\"",
+        header
+    )))
+}
+
 impl ImprovementChoiceSetGenerator {
-    pub fn get(&mut self, improvement_type: ImprovementType) -> ImprovementChoiceSet {
+    pub fn new(ability_slots: usize, ruleset: &Ruleset) -> Self {
+        Self {
+            shield_upgrade_generator: ShieldUpgradeGenerator::default(),
+            coin_purchase_generator: CoinPurchaseGenerator::new(ruleset),
+            experience_point_level_up_generator: ExperiencePointLevelUpGenerator::new(
+                ability_slots,
+            ),
+        }
+    }
+
+    pub fn get(
+        &mut self,
+        improvement_type: ImprovementType,
+        rng: &mut GameRng,
+    ) -> ImprovementChoiceSet {
         let (num_choices, num_to_choose) = improvement_type.x_choose_y();
         let mut displays: Vec<ImprovementChoiceDisplay> = Vec::with_capacity(num_choices);
         match improvement_type {
             ImprovementType::Shields => {
                 let mut shield_upgrades: Vec<ShieldUpgrade> = Vec::with_capacity(num_choices);
                 for pushing_idx in 0..num_choices {
-                    shield_upgrades.push(self.shield_upgrade_generator.get());
+                    shield_upgrades.push(self.shield_upgrade_generator.get(rng));
                     displays.push(ImprovementChoiceDisplay::from(
                         &shield_upgrades[pushing_idx],
                     ));
@@ -84,7 +134,7 @@ impl ImprovementChoiceSetGenerator {
             ImprovementType::Coins => {
                 let mut coin_purchases: Vec<CoinPurchase> = Vec::with_capacity(num_choices);
                 for pushing_idx in 0..num_choices {
-                    coin_purchases.push(self.coin_purchase_generator.get());
+                    coin_purchases.push(self.coin_purchase_generator.get(rng));
                     displays.push(ImprovementChoiceDisplay::from(&coin_purchases[pushing_idx]));
                 }
                 self.coin_purchase_generator.reset();
@@ -103,15 +153,12 @@ impl ImprovementChoiceSetGenerator {
                 for _ in 0..num_choices {
                     // we could obtain None because if all the abilities hit max level
                     // then only stats are available
-                    match self.experience_point_level_up_generator.get() {
-                        Some(xplu) => {
-                            experience_point_level_ups.push(xplu);
-                            displays.push(ImprovementChoiceDisplay::from(
-                                &experience_point_level_ups[pushing_idx],
-                            ));
-                            pushing_idx += 1;
-                        }
-                        None => {}
+                    if let Some(xplu) = self.experience_point_level_up_generator.get(rng) {
+                        experience_point_level_ups.push(xplu);
+                        displays.push(ImprovementChoiceDisplay::from(
+                            &experience_point_level_ups[pushing_idx],
+                        ));
+                        pushing_idx += 1;
                     }
                 }
                 self.experience_point_level_up_generator.reset();
@@ -0,0 +1,139 @@
+use crate::game::board::Board;
+use crate::game::tile::{TilePosition, Wind8};
+
+const NUM_DIRECTIONS: u8 = 8;
+
+// the number of paths grows very quickly with board size, so unlimited
+// enumeration of a full board is only reasonable when stopping early
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct PathLimits {
+    // paths longer than this aren't explored
+    pub max_length: Option<usize>,
+    // iteration stops after this many paths
+    pub max_count: Option<usize>,
+}
+
+// every selection path that would hit if it were submitted, in the order they'd
+// be selected. paths are found depth first from each starting tile in row major
+// order, so a path is always yielded right before the longer paths it starts
+pub struct LegalPaths<'a> {
+    board: &'a Board,
+    limits: PathLimits,
+    next_start: usize,
+    path: Vec<TilePosition>,
+    // for each tile in path, the next Wind8 to try extending the path in
+    next_directions: Vec<u8>,
+    num_found: usize,
+}
+
+impl<'a> LegalPaths<'a> {
+    pub fn new(board: &'a Board, limits: PathLimits) -> Self {
+        Self {
+            board,
+            limits,
+            next_start: 0,
+            path: Vec::with_capacity(board.num_tiles()),
+            next_directions: Vec::with_capacity(board.num_tiles()),
+            num_found: 0,
+        }
+    }
+
+    fn can_extend(&self) -> bool {
+        match self.limits.max_length {
+            Some(max_length) => self.path.len() < max_length,
+            None => true,
+        }
+    }
+
+    fn push(&mut self, tile_position: TilePosition) -> Option<Vec<TilePosition>> {
+        self.path.push(tile_position);
+        self.next_directions.push(0);
+        if self.path.len() >= self.board.min_destruction_selection()
+            && self.path.len() <= self.limits.max_length.unwrap_or(usize::MAX)
+        {
+            self.num_found += 1;
+            Some(self.path.clone())
+        } else {
+            None
+        }
+    }
+}
+
+impl Iterator for LegalPaths<'_> {
+    type Item = Vec<TilePosition>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(max_count) = self.limits.max_count {
+                if self.num_found >= max_count {
+                    return None;
+                }
+            }
+
+            if self.path.is_empty() {
+                if self.next_start >= self.board.num_tiles() {
+                    return None;
+                }
+                let start = TilePosition::new(
+                    (self.next_start / self.board.width()) as isize,
+                    (self.next_start % self.board.width()) as isize,
+                );
+                self.next_start += 1;
+                if let Some(path) = self.push(start) {
+                    return Some(path);
+                }
+                continue;
+            }
+
+            let direction = *self.next_directions.last().expect("");
+            if direction >= NUM_DIRECTIONS || !self.can_extend() {
+                self.path.pop();
+                self.next_directions.pop();
+                continue;
+            }
+            *self.next_directions.last_mut().expect("") += 1;
+
+            let w8 = Wind8::try_from(direction).expect("");
+            let candidate = *self.path.last().expect("") + TilePosition::from(w8);
+            let candidate_tile = match self.board.get_tile(&candidate) {
+                Some(tile) => tile,
+                None => continue,
+            };
+            let start_tile_type = self.board.tile_at(&self.path[0]).tile_type;
+            if !start_tile_type.connects_with(candidate_tile.tile_type)
+                || self.path.contains(&candidate)
+            {
+                continue;
+            }
+            if let Some(path) = self.push(candidate) {
+                return Some(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+
+    #[test]
+    fn test_legal_paths_are_selectable() {
+        let game = Game::with_seed(21);
+        let limits = PathLimits {
+            max_length: Some(5),
+            max_count: Some(100),
+        };
+        let paths: Vec<Vec<TilePosition>> = game.legal_paths(limits).collect();
+        assert_eq!(paths.len(), 100);
+        for path in paths.iter() {
+            assert!(path.len() >= game.config().min_destruction_selection() && path.len() <= 5);
+            let mut replayed = Game::with_seed(21);
+            for tile_position in path.iter() {
+                replayed.select_tile(tile_position).expect("");
+            }
+            assert!(&replayed.board_view().selection() == path);
+        }
+        assert!(game.has_legal_path());
+    }
+}
@@ -0,0 +1,29 @@
+use crate::game::tile::{Tile, TilePosition};
+
+// how tiles moved around the board, for animating the changes. applying a list of
+// these in order to the board from before they happened gives the board after
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TileMovement {
+    Fell {
+        from: TilePosition,
+        to: TilePosition,
+    },
+    Spawned {
+        at: TilePosition,
+        tile: Tile,
+    },
+    Swapped {
+        a: TilePosition,
+        b: TilePosition,
+    },
+    Destroyed {
+        at: TilePosition,
+        tile: Tile,
+    },
+    Replaced {
+        at: TilePosition,
+        old: Tile,
+        new: Tile,
+    },
+}
@@ -1,13 +1,17 @@
 use crate::game::abilities::{Ability, AbilityType};
 use crate::game::being::{Being, BeingType};
 use crate::game::coin_purchase::{CoinPurchase, CoinPurchaseInfo};
+use crate::game::config::BeingStartStats;
 use crate::game::experience_point_level_up::{
     ExperiencePointLevelUp, ExperiencePointLevelUpInfo, StatLevelUpInfo,
 };
+use crate::game::ruleset::{AbilityCooldowns, CentsScaling, Ruleset};
 use crate::game::shield_upgrade::{ShieldUpgrade, ShieldUpgradeInfo};
 use crate::game::stat_modifiers::PlayerStatModifiers;
 use crate::game::ABILITY_SLOTS;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct Player {
     pub being: Being,
     pub coin_cents: usize,
@@ -20,29 +24,30 @@ pub struct Player {
     pub abilities: Vec<Option<Ability>>,
 }
 
-// GAME_BALANCE: heck all of this...
-
-pub const STARTING_COIN_CENTS_PER_PURCHASE: usize = 1000;
-pub const NUM_DEN_C_COIN_CENTS_SCALING: (usize, usize, usize) = (99, 100, 500);
-pub const STARTING_EXCESS_SHIELD_CENTS_PER_UPGRADE: usize = 1000;
-pub const NUM_DEN_C_EXCESS_SHIELD_CENTS_SCALING: (usize, usize, usize) = (99, 100, 500);
-pub const STARTING_EXPERIENCE_POINT_CENTS_PER_LEVEL_UP: usize = 300;
-pub const NUM_DEN_C_EXPERIENCE_POINT_CENTS_SCALING: (usize, usize, usize) = (99, 100, 500);
-
 impl Default for Player {
     fn default() -> Self {
-        let mut abilities = Vec::with_capacity(ABILITY_SLOTS);
-        for _ in 0..ABILITY_SLOTS {
+        Self::new(
+            &BeingStartStats::from(BeingType::Player),
+            ABILITY_SLOTS,
+            &Ruleset::default(),
+        )
+    }
+}
+
+impl Player {
+    pub fn new(start_stats: &BeingStartStats, ability_slots: usize, ruleset: &Ruleset) -> Self {
+        let mut abilities = Vec::with_capacity(ability_slots);
+        for _ in 0..ability_slots {
             abilities.push(None);
         }
         Self {
-            being: Being::new(BeingType::Player, 1, 1),
+            being: Being::new(BeingType::Player, start_stats, 1, 1),
             coin_cents: 0,
-            coin_cents_per_purchase: STARTING_COIN_CENTS_PER_PURCHASE,
+            coin_cents_per_purchase: ruleset.coin_cents.starting_cents,
             excess_shield_cents: 0,
-            excess_shield_cents_per_upgrade: STARTING_EXCESS_SHIELD_CENTS_PER_UPGRADE,
+            excess_shield_cents_per_upgrade: ruleset.excess_shield_cents.starting_cents,
             experience_point_cents: 0,
-            experience_point_cents_per_level_up: STARTING_EXPERIENCE_POINT_CENTS_PER_LEVEL_UP,
+            experience_point_cents_per_level_up: ruleset.experience_point_cents.starting_cents,
             stat_modifiers: PlayerStatModifiers::default(),
             abilities,
         }
@@ -52,7 +57,7 @@ impl Default for Player {
 type NumRollovers = usize;
 fn rollover_add(val_into: &mut usize, val_other: usize, exclusive_max: usize) -> NumRollovers {
     *val_into += val_other;
-    let divi = *val_into as usize / exclusive_max as usize;
+    let divi = *val_into / exclusive_max;
     let modu = *val_into % exclusive_max;
     *val_into = modu;
     divi
@@ -76,44 +81,82 @@ impl Player {
             .add_hit_points(potions_collected * self.stat_modifiers.hit_points_per_potion)
     }
 
-    pub fn add_coins(&mut self, coin_tiles_collected: usize) -> NumPurchases {
+    // the read only versions of the add functions below, for previewing a selection
+
+    pub fn hit_points_restored_by(&self, potions_collected: usize) -> usize {
+        let hit_points = potions_collected * self.stat_modifiers.hit_points_per_potion;
+        let mut being = self.being;
+        hit_points - being.add_hit_points(hit_points)
+    }
+
+    pub fn coin_purchases_from(&self, coin_tiles_collected: usize) -> NumPurchases {
+        (self.coin_cents + coin_tiles_collected * self.stat_modifiers.percent_gold_per_coin)
+            / self.coin_cents_per_purchase
+    }
+
+    pub fn shield_upgrades_from(&self, shield_tiles_collected: usize) -> NumUpgrades {
+        let mut being = self.being;
+        let excess =
+            being.add_shields(shield_tiles_collected, self.stat_modifiers.armor_per_shield);
+        (self.excess_shield_cents + excess * self.stat_modifiers.percent_upgrade_points_per_shield)
+            / self.excess_shield_cents_per_upgrade
+    }
+
+    pub fn level_ups_from(&self, experience_point_tiles_collected: usize) -> NumLevelUps {
+        (self.experience_point_cents
+            + experience_point_tiles_collected
+                * self.stat_modifiers.percent_xp_per_experience_point)
+            / self.experience_point_cents_per_level_up
+    }
+
+    pub fn add_coins(
+        &mut self,
+        coin_tiles_collected: usize,
+        cents_scaling: &CentsScaling,
+    ) -> NumPurchases {
         let rollover = rollover_add(
             &mut self.coin_cents,
             coin_tiles_collected * self.stat_modifiers.percent_gold_per_coin,
             self.coin_cents_per_purchase,
         );
         for _ in 0..rollover {
-            self.coin_cents_per_purchase *= NUM_DEN_C_COIN_CENTS_SCALING.0;
-            self.coin_cents_per_purchase /= NUM_DEN_C_COIN_CENTS_SCALING.1;
-            self.coin_cents_per_purchase += NUM_DEN_C_COIN_CENTS_SCALING.2;
+            self.coin_cents_per_purchase = cents_scaling.scale(self.coin_cents_per_purchase);
         }
         rollover
     }
 
-    fn add_excess_shields(&mut self, excess_shields_to_add: usize) -> NumUpgrades {
+    fn add_excess_shields(
+        &mut self,
+        excess_shields_to_add: usize,
+        cents_scaling: &CentsScaling,
+    ) -> NumUpgrades {
         let rollover = rollover_add(
             &mut self.excess_shield_cents,
             excess_shields_to_add * self.stat_modifiers.percent_upgrade_points_per_shield,
             self.excess_shield_cents_per_upgrade,
         );
         for _ in 0..rollover {
-            self.excess_shield_cents_per_upgrade *= NUM_DEN_C_EXCESS_SHIELD_CENTS_SCALING.0;
-            self.excess_shield_cents_per_upgrade /= NUM_DEN_C_EXCESS_SHIELD_CENTS_SCALING.1;
-            self.excess_shield_cents_per_upgrade += NUM_DEN_C_EXCESS_SHIELD_CENTS_SCALING.2;
+            self.excess_shield_cents_per_upgrade =
+                cents_scaling.scale(self.excess_shield_cents_per_upgrade);
         }
         rollover
     }
 
-    pub fn add_shields(&mut self, shield_tiles_collected: usize) -> NumUpgrades {
+    pub fn add_shields(
+        &mut self,
+        shield_tiles_collected: usize,
+        cents_scaling: &CentsScaling,
+    ) -> NumUpgrades {
         let excess = self
             .being
             .add_shields(shield_tiles_collected, self.stat_modifiers.armor_per_shield);
-        self.add_excess_shields(excess)
+        self.add_excess_shields(excess, cents_scaling)
     }
 
     pub fn add_experience_points(
         &mut self,
         experience_point_tiles_collected: usize,
+        cents_scaling: &CentsScaling,
     ) -> NumLevelUps {
         let rollover = rollover_add(
             &mut self.experience_point_cents,
@@ -121,9 +164,8 @@ impl Player {
             self.experience_point_cents_per_level_up,
         );
         for _ in 0..rollover {
-            self.experience_point_cents_per_level_up *= NUM_DEN_C_EXPERIENCE_POINT_CENTS_SCALING.0;
-            self.experience_point_cents_per_level_up /= NUM_DEN_C_EXPERIENCE_POINT_CENTS_SCALING.1;
-            self.experience_point_cents_per_level_up += NUM_DEN_C_EXPERIENCE_POINT_CENTS_SCALING.2;
+            self.experience_point_cents_per_level_up =
+                cents_scaling.scale(self.experience_point_cents_per_level_up);
         }
         rollover
     }
@@ -166,7 +208,11 @@ impl Player {
         };
     }
 
-    fn handle_ability_level_up(&mut self, ability_type: AbilityType) -> usize {
+    fn handle_ability_level_up(
+        &mut self,
+        ability_type: AbilityType,
+        ability_cooldowns: &AbilityCooldowns,
+    ) -> usize {
         for ability_opt in self.abilities.iter_mut() {
             match ability_opt {
                 Some(ref mut a) => {
@@ -176,7 +222,10 @@ impl Player {
                     }
                 }
                 None => {
-                    *ability_opt = Some(Ability::new(ability_type));
+                    *ability_opt = Some(Ability::new(
+                        ability_type,
+                        ability_cooldowns.cooldown(ability_type),
+                    ));
                     return ability_opt.as_ref().expect("").current_level;
                 }
             }
@@ -185,9 +234,15 @@ impl Player {
     }
 
     // returns the level of the leveled up ability if an ability was upgraded and 0 otherwise
-    pub fn apply_level_up(&mut self, level_up: &ExperiencePointLevelUp) -> usize {
+    pub fn apply_level_up(
+        &mut self,
+        level_up: &ExperiencePointLevelUp,
+        ability_cooldowns: &AbilityCooldowns,
+    ) -> usize {
         match level_up.experience_point_level_up_info {
-            ExperiencePointLevelUpInfo::Ability(atype) => self.handle_ability_level_up(atype),
+            ExperiencePointLevelUpInfo::Ability(atype) => {
+                self.handle_ability_level_up(atype, ability_cooldowns)
+            }
             ExperiencePointLevelUpInfo::Stat(sluinfo) => {
                 match sluinfo {
                     StatLevelUpInfo::MaxHitPoints(max_hp_inc) => {
@@ -0,0 +1,49 @@
+use crate::game::tile::{Tile, TilePosition, TileType};
+
+// a selected tile after it would be hit by the current selection
+#[derive(Copy, Clone)]
+pub struct SelectionHit {
+    pub position: TilePosition,
+    pub tile: Tile,
+    pub damage: usize,
+    pub destroyed: bool,
+    // an undead special that took its first killing blow
+    pub reanimated: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TilePreview {
+    pub position: TilePosition,
+    pub tile_type: TileType,
+    // only enemies and specials take damage, everything else is just collected
+    pub damage: usize,
+    pub destroyed: bool,
+}
+
+impl From<&SelectionHit> for TilePreview {
+    fn from(value: &SelectionHit) -> Self {
+        Self {
+            position: value.position,
+            tile_type: value.tile.tile_type,
+            damage: value.damage,
+            destroyed: value.destroyed,
+        }
+    }
+}
+
+// what submitting the current selection would do, everything is 0 if it wouldn't hit
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct SelectionPreview {
+    pub hit: bool,
+    // in selection order
+    pub tiles: Vec<TilePreview>,
+    pub potions: usize,
+    pub hit_points_restored: usize,
+    pub shields: usize,
+    pub coins: usize,
+    pub experience_points: usize,
+    // how many times each improvement meter would roll over
+    pub shield_upgrades: usize,
+    pub coin_purchases: usize,
+    pub level_ups: usize,
+}
@@ -1,5 +1,16 @@
+use rand::{Rng, SeedableRng};
+
 pub type Weight = usize;
 
+// every random draw in the game goes through this so that a seed fully determines a run
+pub type GameRng = rand_chacha::ChaCha8Rng;
+
+pub fn rng_from_seed(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 struct ValueWeight {
     value: usize,
     weight: Weight,
@@ -17,18 +28,22 @@ impl ValueWeight {
 
     fn true_weight(&self) -> Weight {
         if self.weight_meta_modifier < 0 {
-            self.weight - ((-1) * self.weight_meta_modifier) as usize
+            self.weight - (-self.weight_meta_modifier) as usize
         } else {
             self.weight + self.weight_meta_modifier as usize
         }
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub enum WeightedRandomizerType {
     Default,
     MetaSubAllOnObtain,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct WeightedRandomizer {
     weighted_randomizer_type: WeightedRandomizerType,
     value_weight_vec: Vec<ValueWeight>,
@@ -48,19 +63,19 @@ impl Default for WeightedRandomizer {
     }
 }
 
-pub fn evenly_distributed_random(max_value: usize) -> usize {
+pub fn evenly_distributed_random(max_value: usize, rng: &mut GameRng) -> usize {
     if max_value == usize::MAX {
-        return rand::random::<usize>();
+        return rng.gen::<usize>();
     }
     let get_value_below = max_value + 1;
     // if power of 2
     if get_value_below & (get_value_below - 1) == 0 {
-        return rand::random::<usize>() % get_value_below;
+        rng.gen::<usize>() % get_value_below
     } else {
         let remainder = usize::MAX % get_value_below;
         let threshold = usize::MAX - remainder;
         loop {
-            let candidate = rand::random::<usize>();
+            let candidate = rng.gen::<usize>();
             if candidate < threshold {
                 return candidate % get_value_below;
             }
@@ -101,11 +116,11 @@ impl WeightedRandomizer {
             -(self.value_weight_vec[idx].weight as isize);
     }
 
-    pub fn weighted_random(&mut self) -> Option<usize> {
+    pub fn weighted_random(&mut self, rng: &mut GameRng) -> Option<usize> {
         if self.total_weight == 0 {
             return None;
         }
-        let random_num = evenly_distributed_random(self.total_weight - 1);
+        let random_num = evenly_distributed_random(self.total_weight - 1, rng);
         let mut running_sum = 0;
         for idx in 0..self.value_weight_vec.len() {
             running_sum += self.value_weight_vec[idx].true_weight();
@@ -126,9 +141,9 @@ impl WeightedRandomizer {
     fn find(&self, value: usize) -> Result<usize, ()> {
         if self.indexed {
             if value < self.value_weight_vec.len() {
-                return Ok(value);
+                Ok(value)
             } else {
-                return Err(());
+                Err(())
             }
         } else {
             for idx in 0..self.value_weight_vec.len() {
@@ -138,23 +153,23 @@ impl WeightedRandomizer {
                     return Err(());
                 }
             }
-            return Err(());
+            Err(())
         }
     }
 
     fn true_find(&mut self, value: usize) -> usize {
         if self.indexed {
             if value < self.value_weight_vec.len() {
-                return value;
+                value
             } else if value < self.value_weight_vec.len() + MAX_VALUE_SEPARATION {
                 for idx in self.value_weight_vec.len()..=value {
                     self.value_weight_vec.push(ValueWeight::new(idx));
                 }
-                return value;
+                value
             } else {
                 self.indexed = false;
                 self.value_weight_vec.push(ValueWeight::new(value));
-                return self.value_weight_vec.len() - 1;
+                self.value_weight_vec.len() - 1
             }
         } else {
             for idx in 0..self.value_weight_vec.len() {
@@ -166,7 +181,7 @@ impl WeightedRandomizer {
                 }
             }
             self.value_weight_vec.push(ValueWeight::new(value));
-            return self.value_weight_vec.len() - 1;
+            self.value_weight_vec.len() - 1
         }
     }
 
@@ -175,8 +190,8 @@ impl WeightedRandomizer {
         let vw = &mut self.value_weight_vec[idx];
         let old_true_weight = vw.true_weight();
         vw.weight = new_weight;
-        if vw.weight_meta_modifier < 0 && ((-1) * vw.weight_meta_modifier) as usize > vw.weight {
-            vw.weight_meta_modifier = vw.weight as isize * (-1);
+        if vw.weight_meta_modifier < 0 && (-vw.weight_meta_modifier) as usize > vw.weight {
+            vw.weight_meta_modifier = -(vw.weight as isize);
         }
         let new_true_weight = vw.true_weight();
         self.weight_update(old_true_weight, new_true_weight);
@@ -211,14 +226,31 @@ mod tests {
 
     #[test]
     fn test_weighted_randomizer() {
+        let mut rng = rng_from_seed(0);
         let mut wr = WeightedRandomizer::default();
         wr.set_weight(0, 4);
-        assert_eq!(wr.weighted_random(), Some(0));
+        assert_eq!(wr.weighted_random(&mut rng), Some(0));
         wr.set_weight(1, 1);
         for i in 0..100 {
-            let wr_num = wr.weighted_random();
-            wr.set_weight(i + 2, rand::random::<usize>() % 100);
+            let wr_num = wr.weighted_random(&mut rng);
+            wr.set_weight(i + 2, rng.gen::<usize>() % 100);
             assert!(wr_num.is_some() && wr_num.expect("") < i + 2);
         }
     }
+
+    #[test]
+    fn test_weighted_randomizer_same_seed_same_draws() {
+        let draws = |seed: u64| {
+            let mut rng = rng_from_seed(seed);
+            let mut wr = WeightedRandomizer::default();
+            for value in 0..10 {
+                wr.set_weight(value, value + 1);
+            }
+            (0..50)
+                .map(|_| wr.weighted_random(&mut rng).expect(""))
+                .collect::<Vec<usize>>()
+        };
+        assert_eq!(draws(7), draws(7));
+        assert_ne!(draws(7), draws(8));
+    }
 }
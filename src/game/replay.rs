@@ -0,0 +1,162 @@
+use crate::game::config::GameConfig;
+use crate::game::error::GameError;
+use crate::game::tile::TilePosition;
+use crate::game::Game;
+
+// every player input that can change a Game
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GameAction {
+    SelectTile(TilePosition),
+    BacktrackSelection,
+    CancelSelection,
+    SubmitTurn,
+    CastAbility(usize),
+    ChooseImprovements(Vec<usize>),
+}
+
+#[derive(Clone)]
+pub struct Replay {
+    game: Game,
+    actions: Vec<GameAction>,
+    next_action: usize,
+}
+
+impl Replay {
+    // the config should carry the seed of the game being replayed
+    pub fn new(config: GameConfig, actions: Vec<GameAction>) -> Self {
+        Self {
+            game: Game::new(config),
+            actions,
+            next_action: 0,
+        }
+    }
+
+    pub fn from_game(game: &Game) -> Self {
+        Self::new(game.config().clone(), game.action_log().to_vec())
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn actions(&self) -> &[GameAction] {
+        &self.actions
+    }
+
+    // index of the action that the next call to step will execute
+    pub fn position(&self) -> usize {
+        self.next_action
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_action >= self.actions.len()
+    }
+
+    // executes the next action and returns it, or None if the replay is finished.
+    // an action the game rejects is still stepped past
+    pub fn step(&mut self) -> Option<Result<&GameAction, GameError>> {
+        let action = self.actions.get(self.next_action)?;
+        self.next_action += 1;
+        Some(self.game.apply_action(action).map(|_| action))
+    }
+
+    // stops at the first rejected action
+    pub fn run_to_end(&mut self) -> Result<&Game, GameError> {
+        while let Some(result) = self.step() {
+            result?;
+        }
+        Ok(&self.game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::randomizer::rng_from_seed;
+    use crate::game::tile::Wind8;
+    use crate::game::turn::TurnPhase;
+    use crate::game::{DEFAULT_BOARD_HEIGHT, DEFAULT_BOARD_WIDTH};
+    use rand::Rng;
+
+    fn testhelp_play(game: &mut Game, turns: usize) {
+        let mut rng = rng_from_seed(game.seed() + 1);
+        for _ in 0..turns {
+            if game.phase() == TurnPhase::GameOver {
+                return;
+            }
+            if let Some(set) = game.improvement_choice_set() {
+                let indeces: Vec<usize> = (0..set.num_to_choose).collect();
+                game.choose_improvements(&indeces).expect("");
+                continue;
+            }
+            let _ = game.cast_ability(rng.gen_range(0..crate::game::ABILITY_SLOTS));
+            let lines = crate::game::tests::testhelp_connected_lines(game);
+            if lines.is_empty() {
+                return;
+            }
+            for tile_position in lines[rng.gen_range(0..lines.len())].iter() {
+                game.select_tile(tile_position).expect("");
+            }
+            game.submit_turn().expect("");
+        }
+    }
+
+    fn testhelp_fingerprint(game: &Game) -> Vec<String> {
+        let mut fingerprint = vec![];
+        for y in 0..DEFAULT_BOARD_HEIGHT {
+            for x in 0..DEFAULT_BOARD_WIDTH {
+                let tile = game
+                    .get_tile(&TilePosition::new(y as isize, x as isize))
+                    .expect("");
+                fingerprint.push(format!(
+                    "{} {}",
+                    tile.tile_type as usize,
+                    tile.next_selection != Wind8::None
+                ));
+            }
+        }
+        let being = &game.player().being;
+        fingerprint.push(format!(
+            "{} {} {} {}",
+            being.hit_points, being.shields, being.base_output_damage, game.turns_passed
+        ));
+        fingerprint
+    }
+
+    #[test]
+    fn test_replay_reproduces_game() {
+        let mut game = Game::with_seed(5);
+        testhelp_play(&mut game, 200);
+        assert!(game.turns_passed > 0);
+
+        let mut replay = Replay::from_game(&game);
+        assert_eq!(replay.actions(), game.action_log());
+        replay.run_to_end().expect("");
+        assert!(replay.is_finished());
+        assert_eq!(
+            testhelp_fingerprint(replay.game()),
+            testhelp_fingerprint(&game)
+        );
+        assert_eq!(replay.game().action_log(), game.action_log());
+    }
+
+    #[test]
+    fn test_replay_steps_one_action_at_a_time() {
+        let mut game = Game::with_seed(8);
+        testhelp_play(&mut game, 20);
+
+        let mut replay = Replay::new(game.config().clone(), game.action_log().to_vec());
+        let mut partial = Game::with_seed(game.seed());
+        for idx in 0..game.action_log().len() {
+            assert_eq!(replay.position(), idx);
+            let action = replay.step().expect("").expect("").clone();
+            partial.apply_action(&action).expect("");
+            assert_eq!(
+                testhelp_fingerprint(replay.game()),
+                testhelp_fingerprint(&partial)
+            );
+        }
+        assert!(replay.step().is_none());
+    }
+}
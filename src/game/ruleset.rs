@@ -0,0 +1,436 @@
+use crate::game::abilities::{AbilityType, MAX_ABILITY_LEVEL};
+use crate::game::error::RulesetError;
+use crate::game::randomizer::Weight;
+use crate::game::special::SpecialType;
+
+// GAME_BALANCE: every number in here is meant to be tuned, either by editing
+// the defaults below or by loading a ruleset file over them
+
+#[cfg(feature = "toml")]
+pub const DEFAULT_RULESET_TOML: &str = include_str!("../../rulesets/default.toml");
+#[cfg(feature = "json")]
+pub const DEFAULT_RULESET_JSON: &str = include_str!("../../rulesets/default.json");
+
+// how many cents the next improvement costs, and how that cost grows each time
+// one is earned: next = (current * numerator) / denominator + constant
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CentsScaling {
+    pub starting_cents: usize,
+    pub numerator: usize,
+    pub denominator: usize,
+    pub constant: usize,
+}
+
+impl CentsScaling {
+    pub fn scale(&self, cents: usize) -> usize {
+        (cents.saturating_mul(self.numerator) / self.denominator).saturating_add(self.constant)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ScaleRatio {
+    pub numerator: usize,
+    pub denominator: usize,
+}
+
+impl From<(usize, usize)> for ScaleRatio {
+    fn from((numerator, denominator): (usize, usize)) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+}
+
+// how each special's stats are scaled from the special start stats
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SpecialScaling {
+    pub boss: ScaleRatio,
+    pub chaotic: ScaleRatio,
+    pub precise: ScaleRatio,
+    pub undead: ScaleRatio,
+    pub resourceful: ScaleRatio,
+    pub enlightener: ScaleRatio,
+    pub kamikaze: ScaleRatio,
+}
+
+impl SpecialScaling {
+    pub fn ratio(&self, special_type: SpecialType) -> ScaleRatio {
+        match special_type {
+            SpecialType::Boss => self.boss,
+            SpecialType::Chaotic => self.chaotic,
+            SpecialType::Precise => self.precise,
+            SpecialType::Undead => self.undead,
+            SpecialType::Resourceful => self.resourceful,
+            SpecialType::Enlightener => self.enlightener,
+            SpecialType::Kamikaze => self.kamikaze,
+            SpecialType::COUNT => unreachable!(""),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AbilityCooldowns {
+    pub double_shield_collection: usize,
+    pub double_coin_collection: usize,
+    pub double_weapon_collection: usize,
+    pub enemies_to_coins: usize,
+    pub scramble_board: usize,
+}
+
+impl AbilityCooldowns {
+    pub fn cooldown(&self, ability_type: AbilityType) -> usize {
+        match ability_type {
+            AbilityType::DoubleShieldCollection => self.double_shield_collection,
+            AbilityType::DoubleCoinCollection => self.double_coin_collection,
+            AbilityType::DoubleWeaponCollection => self.double_weapon_collection,
+            AbilityType::EnemiesToCoins => self.enemies_to_coins,
+            AbilityType::ScrambleBoard => self.scramble_board,
+            AbilityType::COUNT => unreachable!(""),
+        }
+    }
+}
+
+// enemies and specials created after turns_passed turns have their stats scaled
+// to 100% + percent_per_step% for every turns_per_step turns, up to max_extra_percent% extra
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DifficultyCurve {
+    pub turns_per_step: usize,
+    pub percent_per_step: usize,
+    pub max_extra_percent: usize,
+}
+
+impl DifficultyCurve {
+    pub fn percent(&self, turns_passed: usize) -> usize {
+        let extra = (turns_passed / self.turns_per_step).saturating_mul(self.percent_per_step);
+        std::cmp::min(extra, self.max_extra_percent).saturating_add(100)
+    }
+}
+
+// one possible roll for how much a coin purchase increases a stat by
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct IncreaseWeight {
+    pub increase: usize,
+    pub weight: Weight,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Ruleset {
+    pub coin_cents: CentsScaling,
+    pub excess_shield_cents: CentsScaling,
+    pub experience_point_cents: CentsScaling,
+    pub ability_cooldowns: AbilityCooldowns,
+    pub special_scaling: SpecialScaling,
+    pub difficulty_curve: DifficultyCurve,
+    pub enlighten_cooldown: usize,
+    pub kamikaze_countdown: usize,
+    pub defense_purchase_weights: Vec<IncreaseWeight>,
+    pub attack_purchase_weights: Vec<IncreaseWeight>,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        let cents_scaling = |starting_cents| CentsScaling {
+            starting_cents,
+            numerator: 99,
+            denominator: 100,
+            constant: 500,
+        };
+        let purchase_weights = vec![
+            IncreaseWeight {
+                increase: 1,
+                weight: 99,
+            },
+            IncreaseWeight {
+                increase: 2,
+                weight: 1,
+            },
+        ];
+        Self {
+            coin_cents: cents_scaling(1000),
+            excess_shield_cents: cents_scaling(1000),
+            experience_point_cents: cents_scaling(300),
+            ability_cooldowns: AbilityCooldowns {
+                double_shield_collection: 19,
+                double_coin_collection: 17,
+                double_weapon_collection: 15,
+                enemies_to_coins: 23,
+                scramble_board: 14,
+            },
+            special_scaling: SpecialScaling {
+                boss: ScaleRatio::from((2, 1)),
+                chaotic: ScaleRatio::from((4, 3)),
+                precise: ScaleRatio::from((1, 1)),
+                undead: ScaleRatio::from((4, 3)),
+                resourceful: ScaleRatio::from((1, 1)),
+                enlightener: ScaleRatio::from((2, 3)),
+                kamikaze: ScaleRatio::from((9, 7)),
+            },
+            difficulty_curve: DifficultyCurve {
+                turns_per_step: 10,
+                percent_per_step: 10,
+                max_extra_percent: 200,
+            },
+            enlighten_cooldown: 3, // 4?
+            kamikaze_countdown: 4,
+            defense_purchase_weights: purchase_weights.clone(),
+            attack_purchase_weights: purchase_weights,
+        }
+    }
+}
+
+impl Ruleset {
+    pub fn validate(&self) -> Result<(), RulesetError> {
+        for (field, cents_scaling) in [
+            ("coin_cents", &self.coin_cents),
+            ("excess_shield_cents", &self.excess_shield_cents),
+            ("experience_point_cents", &self.experience_point_cents),
+        ] {
+            if cents_scaling.starting_cents == 0 {
+                return Err(RulesetError::ZeroStartingCents(field));
+            }
+            if cents_scaling.denominator == 0 {
+                return Err(RulesetError::ZeroDenominator(field));
+            }
+            // the cost of an improvement should never be able to drop to 0, which
+            // without a constant means it can never be allowed to shrink
+            if cents_scaling.constant == 0 && cents_scaling.numerator < cents_scaling.denominator {
+                return Err(RulesetError::CentsScaleToZero(field));
+            }
+        }
+
+        let scaling = &self.special_scaling;
+        for (field, ratio) in [
+            ("special_scaling.boss", &scaling.boss),
+            ("special_scaling.chaotic", &scaling.chaotic),
+            ("special_scaling.precise", &scaling.precise),
+            ("special_scaling.undead", &scaling.undead),
+            ("special_scaling.resourceful", &scaling.resourceful),
+            ("special_scaling.enlightener", &scaling.enlightener),
+            ("special_scaling.kamikaze", &scaling.kamikaze),
+        ] {
+            if ratio.denominator == 0 {
+                return Err(RulesetError::ZeroDenominator(field));
+            }
+        }
+
+        if self.difficulty_curve.turns_per_step == 0 {
+            return Err(RulesetError::ZeroValue("difficulty_curve.turns_per_step"));
+        }
+
+        // every level up past the first takes a turn off of the cooldown
+        let cooldowns = &self.ability_cooldowns;
+        for (field, cooldown) in [
+            (
+                "ability_cooldowns.double_shield_collection",
+                cooldowns.double_shield_collection,
+            ),
+            (
+                "ability_cooldowns.double_coin_collection",
+                cooldowns.double_coin_collection,
+            ),
+            (
+                "ability_cooldowns.double_weapon_collection",
+                cooldowns.double_weapon_collection,
+            ),
+            (
+                "ability_cooldowns.enemies_to_coins",
+                cooldowns.enemies_to_coins,
+            ),
+            ("ability_cooldowns.scramble_board", cooldowns.scramble_board),
+        ] {
+            if cooldown < MAX_ABILITY_LEVEL {
+                return Err(RulesetError::CooldownTooShort {
+                    field,
+                    cooldown,
+                    min: MAX_ABILITY_LEVEL,
+                });
+            }
+        }
+
+        for (field, weights) in [
+            ("defense_purchase_weights", &self.defense_purchase_weights),
+            ("attack_purchase_weights", &self.attack_purchase_weights),
+        ] {
+            if weights.iter().all(|iw| iw.weight == 0) {
+                return Err(RulesetError::NoPurchaseWeights(field));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "toml")]
+impl Ruleset {
+    pub fn from_toml_str(s: &str) -> Result<Ruleset, RulesetError> {
+        let ruleset: Ruleset =
+            toml::from_str(s).map_err(|e| RulesetError::Parse(e.message().to_string()))?;
+        ruleset.validate()?;
+        Ok(ruleset)
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(self).expect("a Ruleset should always serialize to toml")
+    }
+}
+
+#[cfg(feature = "json")]
+impl Ruleset {
+    pub fn from_json_str(s: &str) -> Result<Ruleset, RulesetError> {
+        let ruleset: Ruleset =
+            serde_json::from_str(s).map_err(|e| RulesetError::Parse(e.to_string()))?;
+        ruleset.validate()?;
+        Ok(ruleset)
+    }
+
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a Ruleset should always serialize to json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_ruleset_is_valid() {
+        assert_eq!(Ruleset::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_difficulty_curve() {
+        let curve = DifficultyCurve {
+            turns_per_step: 5,
+            percent_per_step: 20,
+            max_extra_percent: 50,
+        };
+        assert_eq!(curve.percent(0), 100);
+        assert_eq!(curve.percent(4), 100);
+        assert_eq!(curve.percent(5), 120);
+        assert_eq!(curve.percent(14), 140);
+        assert_eq!(curve.percent(15), 150);
+        assert_eq!(curve.percent(1000), 150);
+
+        let curve = DifficultyCurve {
+            turns_per_step: 1,
+            percent_per_step: usize::MAX,
+            max_extra_percent: usize::MAX,
+        };
+        assert_eq!(curve.percent(2), usize::MAX);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut ruleset = Ruleset::default();
+        ruleset.experience_point_cents.denominator = 0;
+        assert_eq!(
+            ruleset.validate(),
+            Err(RulesetError::ZeroDenominator("experience_point_cents"))
+        );
+
+        let mut ruleset = Ruleset::default();
+        ruleset.ability_cooldowns.scramble_board = 2;
+        assert_eq!(
+            ruleset.validate(),
+            Err(RulesetError::CooldownTooShort {
+                field: "ability_cooldowns.scramble_board",
+                cooldown: 2,
+                min: MAX_ABILITY_LEVEL,
+            })
+        );
+
+        let mut ruleset = Ruleset::default();
+        ruleset.attack_purchase_weights.clear();
+        assert_eq!(
+            ruleset.validate(),
+            Err(RulesetError::NoPurchaseWeights("attack_purchase_weights"))
+        );
+    }
+
+    #[test]
+    fn test_cents_scaling_never_reaches_zero() {
+        let mut ruleset = Ruleset::default();
+        ruleset.coin_cents.numerator = 1;
+        ruleset.coin_cents.denominator = 2;
+        ruleset.coin_cents.constant = 0;
+        assert_eq!(
+            ruleset.validate(),
+            Err(RulesetError::CentsScaleToZero("coin_cents"))
+        );
+
+        for (numerator, denominator, constant) in [(1, 2, 1), (0, 1, 1), (1, 1, 0), (3, 2, 0)] {
+            ruleset.coin_cents = CentsScaling {
+                starting_cents: 100,
+                numerator,
+                denominator,
+                constant,
+            };
+            assert_eq!(ruleset.validate(), Ok(()));
+            let mut cents = ruleset.coin_cents.starting_cents;
+            for _ in 0..200 {
+                cents = ruleset.coin_cents.scale(cents);
+                assert!(cents > 0);
+            }
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_ruleset() {
+        assert_eq!(
+            Ruleset::from_toml_str(DEFAULT_RULESET_TOML),
+            Ok(Ruleset::default())
+        );
+        let ruleset = Ruleset::default();
+        assert_eq!(
+            Ruleset::from_toml_str(&ruleset.to_toml_string()),
+            Ok(ruleset)
+        );
+
+        let typo = DEFAULT_RULESET_TOML.replace("kamikaze_countdown", "kamikaze_count");
+        match Ruleset::from_toml_str(&typo) {
+            Err(RulesetError::Parse(msg)) => assert!(msg.contains("kamikaze_count")),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+
+        // 99/100 with no constant would shrink coin costs all the way to 0
+        let shrinking = DEFAULT_RULESET_TOML.replacen("constant = 500", "constant = 0", 1);
+        assert_eq!(
+            Ruleset::from_toml_str(&shrinking),
+            Err(RulesetError::CentsScaleToZero("coin_cents"))
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_ruleset() {
+        assert_eq!(
+            Ruleset::from_json_str(DEFAULT_RULESET_JSON),
+            Ok(Ruleset::default())
+        );
+        let ruleset = Ruleset::default();
+        assert_eq!(
+            Ruleset::from_json_str(&ruleset.to_json_string()),
+            Ok(ruleset)
+        );
+        assert!(matches!(
+            Ruleset::from_json_str("{}"),
+            Err(RulesetError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::game::being::Being;
+use crate::game::improvement_choices::ImprovementType;
+use crate::game::special::SpecialType;
+
+const NUM_SPECIAL_TYPES: usize = SpecialType::COUNT as usize;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CauseOfDeath {
+    // killed during the enemy phase, along with every special that attacked that turn
+    EnemyAttack {
+        attacking_specials: Vec<SpecialType>,
+    },
+    KamikazeExplosion,
+    // the board was left without a legal path that no reshuffle could fix
+    NoLegalPath,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ChosenImprovement {
+    pub improvement_type: ImprovementType,
+    pub description: String,
+}
+
+// running totals that only exist to build a RunSummary
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Default)]
+pub(crate) struct RunStats {
+    pub enemies_killed: usize,
+    pub specials_killed: [usize; NUM_SPECIAL_TYPES],
+    pub coins_collected: usize,
+    pub shields_collected: usize,
+    pub potions_collected: usize,
+    pub level_ups: usize,
+    pub improvements_chosen: Vec<ChosenImprovement>,
+    pub cause_of_death: Option<CauseOfDeath>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RunSummary {
+    pub turns_survived: usize,
+    pub enemies_killed: usize,
+    // only special types that were killed at least once
+    pub specials_killed: Vec<(SpecialType, usize)>,
+    pub coins_collected: usize,
+    pub shields_collected: usize,
+    pub potions_collected: usize,
+    pub level_ups: usize,
+    pub improvements_chosen: Vec<ChosenImprovement>,
+    pub final_stats: Being,
+    // None while the run is still going
+    pub cause_of_death: Option<CauseOfDeath>,
+}
+
+impl RunStats {
+    pub fn special_killed(&mut self, special_type: SpecialType) {
+        self.specials_killed[special_type as usize] += 1;
+    }
+
+    pub fn summarize(&self, turns_survived: usize, final_stats: Being) -> RunSummary {
+        let mut specials_killed = vec![];
+        for (st, num_killed) in self.specials_killed.iter().enumerate() {
+            if *num_killed > 0 {
+                specials_killed.push((SpecialType::try_from(st).expect(""), *num_killed));
+            }
+        }
+        RunSummary {
+            turns_survived,
+            enemies_killed: self.enemies_killed,
+            specials_killed,
+            coins_collected: self.coins_collected,
+            shields_collected: self.shields_collected,
+            potions_collected: self.potions_collected,
+            level_ups: self.level_ups,
+            improvements_chosen: self.improvements_chosen.clone(),
+            final_stats,
+            cause_of_death: self.cause_of_death.clone(),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::game::abilities::AbilityType;
+use crate::game::agent::Agent;
+use crate::game::error::GameError;
+use crate::game::event::GameEvent;
+use crate::game::run_summary::RunSummary;
+use crate::game::Game;
+
+const NUM_ABILITY_TYPES: usize = AbilityType::COUNT as usize;
+
+pub struct MatchResult {
+    pub summary: RunSummary,
+    // false if the match was stopped at max_turns, or because the agent had nothing
+    // left to do, with the player still alive
+    pub game_over: bool,
+    // only ability types that were cast at least once
+    pub abilities_cast: Vec<(AbilityType, usize)>,
+}
+
+// plays the game with the agent until the player dies, max_turns turns have passed or
+// the agent has no action left.
+// with nobody else around to take them, events and tile movements are taken and
+// dropped along the way. the first action the game rejects ends the match
+pub fn run_match(
+    game: &mut Game,
+    agent: &mut dyn Agent,
+    max_turns: usize,
+) -> Result<MatchResult, GameError> {
+    let mut abilities_cast = [0; NUM_ABILITY_TYPES];
+    while !game.is_game_over() && game.turns_passed < max_turns {
+        let action = match agent.choose_action(game) {
+            Some(action) => action,
+            None => break,
+        };
+        game.apply_agent_action(&action)?;
+        for event in game.take_events() {
+            if let GameEvent::AbilityCast { ability_type, .. } = event {
+                abilities_cast[ability_type as usize] += 1;
+            }
+        }
+        game.take_tile_movements();
+    }
+    let mut abilities = vec![];
+    for (at, num_cast) in abilities_cast.iter().enumerate() {
+        if *num_cast > 0 {
+            abilities.push((AbilityType::try_from(at).expect(""), *num_cast));
+        }
+    }
+    Ok(MatchResult {
+        summary: game.run_summary(),
+        game_over: game.is_game_over(),
+        abilities_cast: abilities,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::agent::{GreedyAgent, RandomAgent};
+    use crate::game::config::GameConfig;
+
+    fn testhelp_game(seed: u64) -> Game {
+        Game::new(
+            GameConfig::builder()
+                .seed(seed)
+                .undo_limit(0)
+                .build()
+                .expect(""),
+        )
+    }
+
+    #[test]
+    fn test_random_agent_plays_to_game_over() {
+        for seed in 0..5 {
+            let mut game = testhelp_game(seed);
+            let result = run_match(&mut game, &mut RandomAgent::new(seed), 1000).expect("");
+            assert!(result.game_over);
+            assert!(result.summary.cause_of_death.is_some());
+            assert_eq!(result.summary.turns_survived, game.turns_passed);
+
+            // agents are seeded too, so a match plays out the same every time
+            let mut again = testhelp_game(seed);
+            run_match(&mut again, &mut RandomAgent::new(seed), 1000).expect("");
+            assert_eq!(again.state_hash(), game.state_hash());
+        }
+    }
+
+    #[test]
+    fn test_greedy_agent_stops_at_max_turns() {
+        let mut game = testhelp_game(2);
+        let result = run_match(&mut game, &mut GreedyAgent::new(), 5).expect("");
+        assert!(!result.game_over);
+        assert!(result.summary.cause_of_death.is_none());
+        assert_eq!(game.turns_passed, 5);
+        assert!(game.take_events().is_empty());
+        assert!(game.take_tile_movements().is_empty());
+    }
+}
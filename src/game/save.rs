@@ -0,0 +1,113 @@
+use crate::game::Game;
+use std::fmt;
+
+// bump whenever a change to the game state would make older saves load incorrectly
+pub const SAVE_VERSION: u32 = 3;
+
+#[derive(serde::Serialize)]
+pub struct SavedGameRef<'a> {
+    version: u32,
+    game: &'a Game,
+}
+
+#[derive(serde::Deserialize)]
+pub struct SavedGame {
+    version: u32,
+    game: Game,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadError {
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "save version {} is not supported (expected {})",
+                version, SAVE_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl SavedGame {
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+impl Game {
+    pub fn to_saved(&self) -> SavedGameRef<'_> {
+        SavedGameRef {
+            version: SAVE_VERSION,
+            game: self,
+        }
+    }
+
+    pub fn from_saved(saved: SavedGame) -> Result<Game, LoadError> {
+        if saved.version != SAVE_VERSION {
+            return Err(LoadError::UnsupportedVersion(saved.version));
+        }
+        // the ruleset is saved twice, so point both back at one copy
+        let mut game = saved.game;
+        let ruleset = game.config.shared_ruleset();
+        game.special_generator.share_ruleset(ruleset);
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::improvement_choices::ImprovementType;
+    use crate::game::turn::TurnPhase;
+
+    fn testhelp_save_string(game: &Game) -> String {
+        serde_json::to_string(&game.to_saved()).expect("")
+    }
+
+    fn testhelp_load_string(s: &str) -> Result<Game, LoadError> {
+        Game::from_saved(serde_json::from_str::<SavedGame>(s).expect(""))
+    }
+
+    #[test]
+    fn test_save_load_resumes_exactly() {
+        let mut game = Game::with_seed(99);
+        game.improvement_queue.push(ImprovementType::Coins);
+        game.improvement_queue
+            .push(ImprovementType::ExperiencePoints);
+        game.select_tile(&crate::game::TilePosition::new(0, 0))
+            .expect("");
+        game.step_improvement_queue();
+        game.phase = TurnPhase::ChoosingImprovement;
+
+        let saved = testhelp_save_string(&game);
+        let mut loaded = testhelp_load_string(&saved).expect("");
+        assert_eq!(saved, testhelp_save_string(&loaded));
+
+        for game in [&mut game, &mut loaded] {
+            game.choose_improvements(&[0, 1]).expect("");
+            game.choose_improvements(&[0]).expect("");
+            game.board.scramble(&mut game.rng);
+        }
+        assert_eq!(testhelp_save_string(&game), testhelp_save_string(&loaded));
+    }
+
+    #[test]
+    fn test_load_rejects_other_versions() {
+        let saved = testhelp_save_string(&Game::with_seed(0)).replacen(
+            &format!("\"version\":{}", SAVE_VERSION),
+            &format!("\"version\":{}", SAVE_VERSION + 1),
+            1,
+        );
+        assert_eq!(
+            testhelp_load_string(&saved).err(),
+            Some(LoadError::UnsupportedVersion(SAVE_VERSION + 1))
+        );
+    }
+}
@@ -1,11 +1,13 @@
 use crate::game::improvement_choices::ImprovementChoiceDisplay;
-use crate::game::randomizer::{WeightedRandomizer, WeightedRandomizerType};
+use crate::game::randomizer::{GameRng, WeightedRandomizer, WeightedRandomizerType};
 use crate::game::stat_modifiers::{
     BaseDamageDecrease, BaseDamageIncrease, DefenseIncrease, HitPointsPerPotionIncrease,
     PercentGoldPerCoinIncrease, PercentUpgradePointsPerShieldIncrease,
 };
 
-#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Hash)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum ShieldUpgradeType {
     Defense,
     BaseDamage,
@@ -32,7 +34,8 @@ impl TryFrom<usize> for ShieldUpgradeType {
     }
 }
 
-#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Hash)]
 pub enum ShieldUpgradeInfo {
     Defense(DefenseIncrease),
     BaseDamage(BaseDamageIncrease),
@@ -42,6 +45,8 @@ pub enum ShieldUpgradeInfo {
     UpgradePointsPerShield(PercentUpgradePointsPerShieldIncrease),
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct ShieldUpgradeGenerator {
     shield_upgrade_type_randomizer: WeightedRandomizer,
 }
@@ -59,6 +64,8 @@ impl Default for ShieldUpgradeGenerator {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct ShieldUpgrade {
     pub shield_upgrade_type: ShieldUpgradeType,
     pub shield_upgrade_info: ShieldUpgradeInfo,
@@ -76,14 +83,13 @@ impl From<&ShieldUpgrade> for ImprovementChoiceDisplay {
             ShieldUpgradeType::UpgradePointsPerShield => "Upgrade-Points per Shield +",
             ShieldUpgradeType::COUNT => unreachable!(""),
         };
-        let info_string;
-        match value.shield_upgrade_info {
-            ShieldUpgradeInfo::Defense(val) => info_string = format!("{}", val),
-            ShieldUpgradeInfo::BaseDamage(val) => info_string = format!("{}", val),
-            ShieldUpgradeInfo::Blunting(val) => info_string = format!("{}", val),
-            ShieldUpgradeInfo::GoldPerCoin(val) => info_string = format!("{}%", val),
-            ShieldUpgradeInfo::HitPointsPerPotion(val) => info_string = format!("{}", val),
-            ShieldUpgradeInfo::UpgradePointsPerShield(val) => info_string = format!("{}%", val),
+        let info_string = match value.shield_upgrade_info {
+            ShieldUpgradeInfo::Defense(val) => format!("{}", val),
+            ShieldUpgradeInfo::BaseDamage(val) => format!("{}", val),
+            ShieldUpgradeInfo::Blunting(val) => format!("{}", val),
+            ShieldUpgradeInfo::GoldPerCoin(val) => format!("{}%", val),
+            ShieldUpgradeInfo::HitPointsPerPotion(val) => format!("{}", val),
+            ShieldUpgradeInfo::UpgradePointsPerShield(val) => format!("{}%", val),
         };
         description += info_string.as_str();
         Self { description }
@@ -91,10 +97,10 @@ impl From<&ShieldUpgrade> for ImprovementChoiceDisplay {
 }
 
 impl ShieldUpgradeGenerator {
-    pub fn get(&mut self) -> ShieldUpgrade {
+    pub fn get(&mut self, rng: &mut GameRng) -> ShieldUpgrade {
         let shield_upgrade_type = ShieldUpgradeType::try_from(
             self.shield_upgrade_type_randomizer
-                .weighted_random()
+                .weighted_random(rng)
                 .expect(""),
         )
         .expect("");
@@ -1,12 +1,19 @@
 use crate::game::being::{Being, BeingType};
-use crate::game::randomizer::WeightedRandomizer;
+use crate::game::config::BeingStartStats;
+use crate::game::event::GameEvent;
+use crate::game::randomizer::{GameRng, WeightedRandomizer};
+use crate::game::ruleset::{Ruleset, SpecialScaling};
+use crate::game::run_summary::CauseOfDeath;
 use crate::game::tile::{TileInfo, TilePosition, TileType};
 use crate::game::Game;
+use std::sync::Arc;
 
 pub type SpecialIdentifier = usize;
 pub type ModifiesBoard = bool;
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum SpecialType {
     Boss,
     Chaotic,
@@ -35,19 +42,23 @@ impl TryFrom<usize> for SpecialType {
     }
 }
 
-impl From<SpecialType> for Being {
-    fn from(value: SpecialType) -> Being {
-        let num_den = match value {
-            SpecialType::Boss => (2, 1),
-            SpecialType::Chaotic => (4, 3),
-            SpecialType::Precise => (1, 1),
-            SpecialType::Undead => (4, 3),
-            SpecialType::Resourceful => (1, 1),
-            SpecialType::Enlightener => (2, 3),
-            SpecialType::Kamikaze => (9, 7),
-            SpecialType::COUNT => unreachable!(""),
-        };
-        let mut being = Being::new(BeingType::Special, num_den.0, num_den.1);
+// the usize is the difficulty percent the special is created at
+impl From<(SpecialType, &BeingStartStats, &SpecialScaling, usize)> for Being {
+    fn from(
+        (value, start_stats, special_scaling, difficulty_percent): (
+            SpecialType,
+            &BeingStartStats,
+            &SpecialScaling,
+            usize,
+        ),
+    ) -> Being {
+        let ratio = special_scaling.ratio(value);
+        let mut being = Being::new(
+            BeingType::Special,
+            start_stats,
+            ratio.numerator.saturating_mul(difficulty_percent),
+            ratio.denominator * 100,
+        );
         if value == SpecialType::Resourceful {
             // the shields being set to 0 should be overridden because
             // end_of_turn is run on specials after they spawn, but in
@@ -62,22 +73,10 @@ impl From<SpecialType> for Being {
 type Reanimated = bool;
 
 type TurnsUntilEnlighten = usize;
-macro_rules! ENLIGHTEN_COOLDOWN_MACRO {
-    () => {
-        3 // GAME_BALANCE: 4?
-    };
-}
-const ENLIGHTEN_COOLDOWN: usize = ENLIGHTEN_COOLDOWN_MACRO!();
-
 type TurnsUntilKamikaze = usize;
-macro_rules! KAMIKAZE_COUNTDOWN_MACRO {
-    () => {
-        4
-    };
-}
-const KAMIKAZE_COUNTDOWN: usize = KAMIKAZE_COUNTDOWN_MACRO!();
 
-#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum SpecialInfo {
     Boss,
     Chaotic,
@@ -88,47 +87,61 @@ pub enum SpecialInfo {
     Kamikaze(TurnsUntilKamikaze),
 }
 
-impl From<SpecialType> for SpecialInfo {
-    fn from(value: SpecialType) -> SpecialInfo {
+impl From<(SpecialType, &Ruleset)> for SpecialInfo {
+    fn from((value, ruleset): (SpecialType, &Ruleset)) -> SpecialInfo {
         match value {
             SpecialType::Boss => Self::Boss,
             SpecialType::Chaotic => Self::Chaotic,
             SpecialType::Precise => Self::Precise,
             SpecialType::Undead => Self::Undead(false),
             SpecialType::Resourceful => Self::Resourceful,
-            SpecialType::Enlightener => Self::Enlightener(ENLIGHTEN_COOLDOWN + 1),
-            SpecialType::Kamikaze => Self::Kamikaze(KAMIKAZE_COUNTDOWN + 1),
+            SpecialType::Enlightener => Self::Enlightener(ruleset.enlighten_cooldown + 1),
+            SpecialType::Kamikaze => Self::Kamikaze(ruleset.kamikaze_countdown + 1),
             SpecialType::COUNT => unreachable!(""),
         }
     }
 }
 
 impl SpecialType {
-    pub fn name_description(self) -> (&'static str, &'static str) {
+    pub fn name_description(self, ruleset: &Ruleset) -> (&'static str, String) {
         match self {
-            Self::Boss => ("Boss", "A much stronger enemy"),
-            Self::Chaotic => ("Chaotic", "Teleports to a random tile every turn"),
-            Self::Precise => ("Precise", "Attacks cannot be blunted"),
+            Self::Boss => ("Boss", String::from("A much stronger enemy")),
+            Self::Chaotic => ("Chaotic", String::from("Teleports to a random tile every turn")),
+            Self::Precise => ("Precise", String::from("Attacks cannot be blunted")),
             Self::Undead => (
                 "Undead",
-                "When killed the first time, reanimates with half HP",
+                String::from("When killed the first time, reanimates with half HP"),
             ),
-			Self::Resourceful => ("Resourceful", "For surrounding tiles, armor = shields, attack += swords, health += health potions"),
+			Self::Resourceful => ("Resourceful", String::from("For surrounding tiles, armor = shields, attack += swords, health += health potions")),
             // RENAME: maybe "regular monster" will be called something different
-            Self::Enlightener => ("Enlightener", concat!("Every ", ENLIGHTEN_COOLDOWN_MACRO!(), " turns, a regular monster into a special monster")),
-            Self::Kamikaze => ("Kamikaze", concat!("Explodes after ", KAMIKAZE_COUNTDOWN_MACRO!(), " turns, dealing half the player's max HP and destroying the surrounding tiles")),
+            Self::Enlightener => ("Enlightener", format!("Every {} turns, a regular monster into a special monster", ruleset.enlighten_cooldown)),
+            Self::Kamikaze => ("Kamikaze", format!("Explodes after {} turns, dealing half the player's max HP and destroying the surrounding tiles", ruleset.kamikaze_countdown)),
             Self::COUNT => unreachable!(""),
         }
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct SpecialGenerator {
     unused_id: SpecialIdentifier,
     type_randomizer: WeightedRandomizer,
+    start_stats: BeingStartStats,
+    ruleset: Arc<Ruleset>,
+    difficulty_percent: usize,
 }
 
 impl Default for SpecialGenerator {
     fn default() -> Self {
+        Self::new(
+            BeingStartStats::from(BeingType::Special),
+            Arc::new(Ruleset::default()),
+        )
+    }
+}
+
+impl SpecialGenerator {
+    pub fn new(start_stats: BeingStartStats, ruleset: Arc<Ruleset>) -> Self {
         let mut type_randomizer = WeightedRandomizer::default();
         for st in 0..(SpecialType::COUNT as usize) {
             type_randomizer.set_weight(st, 1);
@@ -136,11 +149,30 @@ impl Default for SpecialGenerator {
         Self {
             unused_id: 0,
             type_randomizer,
+            start_stats,
+            ruleset,
+            difficulty_percent: 100,
         }
     }
+
+    // the id the next special will get
+    pub fn unused_id(&self) -> SpecialIdentifier {
+        self.unused_id
+    }
+
+    pub fn set_difficulty_percent(&mut self, difficulty_percent: usize) {
+        self.difficulty_percent = difficulty_percent;
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn share_ruleset(&mut self, ruleset: Arc<Ruleset>) {
+        debug_assert!(*self.ruleset == *ruleset);
+        self.ruleset = ruleset;
+    }
 }
 
-#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Special {
     pub id: SpecialIdentifier,
     pub special_type: SpecialType,
@@ -179,16 +211,17 @@ impl Special {
     const END_OF_TURN_TILE_INFO_NOT_SPECIAL: &str = "Special::end_of_turn called with a TilePosition that does not correspond to a TileInfo::Special Tile";
     const SPECIAL_TYPE_SPECIAL_INFO_MISMATCH: &str = "SpecialType and SpecialInfo mismatch";
     pub fn end_of_turn(game: &mut Game, tile_position: &TilePosition) -> ModifiesBoard {
-        let special_type =
+        let (special_type, id) =
             if let TileInfo::Special(ref special) = game.board.tile_at(tile_position).tile_info {
-                special.special_type
+                (special.special_type, special.id)
             } else {
                 unreachable!("{}", Self::END_OF_TURN_TILE_INFO_NOT_SPECIAL);
             };
         match special_type {
             SpecialType::Boss => false,
             SpecialType::Chaotic => {
-                game.board.swap_position_with_random_other(tile_position);
+                game.board
+                    .swap_position_with_random_other(tile_position, &mut game.rng);
                 true
             }
             SpecialType::Precise => false,
@@ -222,8 +255,11 @@ impl Special {
                         special.special_info
                     {
                         if *turns_until_enlighten == 0 {
-                            *turns_until_enlighten = ENLIGHTEN_COOLDOWN;
-                            match game.board.random_tile_of_type(TileType::Enemy) {
+                            *turns_until_enlighten = game.config.ruleset().enlighten_cooldown;
+                            match game
+                                .board
+                                .random_tile_of_type(TileType::Enemy, &mut game.rng)
+                            {
                                 Some(tile_position) => {
                                     //let tile_info = TileInfo::from((TileType::Special, &game.enemy, &mut game.special_generator));
                                     //*game.board.mut_tile_at(&tile_position) = Tile::new(TileType::Special, tile_info);
@@ -232,7 +268,12 @@ impl Special {
                                         TileType::Special,
                                         &game.enemy,
                                         &mut game.special_generator,
+                                        &mut game.rng,
                                     );
+                                    game.events.push(GameEvent::EnlightenerConverted {
+                                        enlightener: id,
+                                        position: tile_position,
+                                    });
                                     true
                                 }
                                 None => false,
@@ -256,16 +297,30 @@ impl Special {
                         special.special_info
                     {
                         if *turns_until_kamikaze == 0 {
+                            game.events.push(GameEvent::KamikazeExploded {
+                                position: *tile_position,
+                                id,
+                            });
                             game.board.destroy_3x3_centered_at(
                                 tile_position,
                                 &game.enemy,
                                 &mut game.special_generator,
+                                &mut game.rng,
                             );
-                            game.player
-                                .take_damage(game.player.being.max_hit_points / 2);
+                            let player_is_dead =
+                                game.damage_player(game.player.being.max_hit_points / 2);
+                            if player_is_dead && game.run_stats.cause_of_death.is_none() {
+                                game.run_stats.cause_of_death =
+                                    Some(CauseOfDeath::KamikazeExplosion);
+                            }
                             true
                         } else {
                             *turns_until_kamikaze -= 1;
+                            game.events.push(GameEvent::KamikazeTicked {
+                                position: *tile_position,
+                                id,
+                                turns_left: *turns_until_kamikaze,
+                            });
                             false
                         }
                     } else {
@@ -281,16 +336,21 @@ impl Special {
 }
 
 impl SpecialGenerator {
-    pub fn get(&mut self) -> Special {
+    pub fn get(&mut self, rng: &mut GameRng) -> Special {
         let special_type =
-            SpecialType::try_from(self.type_randomizer.weighted_random().expect("")).expect("");
+            SpecialType::try_from(self.type_randomizer.weighted_random(rng).expect("")).expect("");
         let id = self.unused_id;
         self.unused_id += 1;
         Special {
             id,
             special_type,
-            special_info: SpecialInfo::from(special_type),
-            being: Being::from(special_type),
+            special_info: SpecialInfo::from((special_type, self.ruleset.as_ref())),
+            being: Being::from((
+                special_type,
+                &self.start_stats,
+                &self.ruleset.special_scaling,
+                self.difficulty_percent,
+            )),
         }
     }
 }
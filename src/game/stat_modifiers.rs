@@ -8,6 +8,8 @@ pub type PercentUpgradePointsPerShieldIncrease = usize;
 pub type PercentXPPerExperiencePointIncrease = usize;
 pub type ArmorPerShield = usize;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Hash)]
 pub struct PlayerStatModifiers {
     //pub defense_increase: DefenseIncrease,
     //pub weapon_damage_increase: WeaponDamageIncrease,
@@ -0,0 +1,134 @@
+use crate::game::Game;
+use std::hash::{Hash, Hasher};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// FNV-1a, since std's DefaultHasher may change between Rust releases. numbers are
+// written little endian and at 64 bits so 32 bit platforms hash the same
+pub struct StateHasher {
+    hash: u64,
+}
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self {
+            hash: FNV_OFFSET_BASIS,
+        }
+    }
+}
+
+impl Hasher for StateHasher {
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes.iter() {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+impl Game {
+    // covers everything that decides how the rest of the run plays out: the board,
+    // player, rng, generators and queued improvements. the config, action log and
+    // run stats are left out, so two games that got to the same state by different
+    // actions hash the same
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::default();
+        self.turns_passed.hash(&mut hasher);
+        self.most_recent_special_kill_turn.hash(&mut hasher);
+        self.min_turns_between_specials.hash(&mut hasher);
+        self.board.hash(&mut hasher);
+        self.player.hash(&mut hasher);
+        self.enemy.hash(&mut hasher);
+        self.special_generator.hash(&mut hasher);
+        self.improvement_choice_set_generator.hash(&mut hasher);
+        self.improvement_choice_set.hash(&mut hasher);
+        self.improvement_queue.hash(&mut hasher);
+        self.collection_multipliers.hash(&mut hasher);
+        self.rng.get_seed().hash(&mut hasher);
+        self.rng.get_stream().hash(&mut hasher);
+        self.rng.get_word_pos().hash(&mut hasher);
+        self.phase.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_hasher_is_fnv1a() {
+        let mut hasher = StateHasher::default();
+        assert_eq!(hasher.finish(), FNV_OFFSET_BASIS);
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+
+        let (mut a, mut b) = (StateHasher::default(), StateHasher::default());
+        a.write_usize(7);
+        b.write_u64(7);
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[test]
+    fn test_state_hash() {
+        let mut game = Game::with_seed(21);
+        let hash = game.state_hash();
+        assert_eq!(Game::with_seed(21).state_hash(), hash);
+        assert_ne!(Game::with_seed(22).state_hash(), hash);
+
+        let mut clone = game.clone();
+        assert_eq!(clone.state_hash(), hash);
+        assert!(std::sync::Arc::ptr_eq(
+            &clone.config.shared_ruleset(),
+            &game.config.shared_ruleset()
+        ));
+
+        let line = crate::game::tests::testhelp_connected_lines(&game)[0];
+        for tile_position in line.iter() {
+            game.select_tile(tile_position).expect("");
+        }
+        assert_ne!(game.state_hash(), hash);
+        game.cancel_selection().expect("");
+        assert_eq!(game.state_hash(), hash);
+
+        for g in [&mut game, &mut clone] {
+            g.submit_path(&line).expect("");
+        }
+        assert_ne!(game.state_hash(), hash);
+        assert_eq!(game.state_hash(), clone.state_hash());
+
+        // only the rng differs
+        let mut clone = game.clone();
+        crate::game::randomizer::evenly_distributed_random(1, &mut clone.rng);
+        assert_ne!(clone.state_hash(), game.state_hash());
+    }
+}
@@ -1,10 +1,11 @@
 use crate::game::being::Being;
-use crate::game::randomizer::Weight;
+use crate::game::randomizer::GameRng;
 use crate::game::special::{Special, SpecialGenerator};
 use std::ops::Add;
 use std::ops::Sub;
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Eq, Hash, PartialEq, Debug)]
 pub enum Wind8 {
     U,
     UR,
@@ -64,7 +65,8 @@ impl TryFrom<TilePosition> for Wind8 {
     }
 }
 
-#[derive(PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 pub struct TilePosition {
     pub y: isize,
     pub x: isize,
@@ -114,7 +116,8 @@ impl From<Wind8> for TilePosition {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum TileType {
     Potion,
     Shield,
@@ -142,50 +145,34 @@ impl TryFrom<usize> for TileType {
     }
 }
 
-impl TryFrom<TileType> for Weight {
-    type Error = &'static str;
-
-    fn try_from(value: TileType) -> Result<Self, Self::Error> {
-        match value {
-            TileType::Potion => Ok(100),
-            TileType::Shield => Ok(100),
-            TileType::Coin => Ok(100),
-            TileType::Sword => Ok(80),
-            TileType::Enemy => Ok(60),
-            TileType::Special => Ok(0),
-            _ => Err("Invalid value for converting TileType->Weight"),
-        }
-    }
-}
-
 impl TileType {
     pub fn connects_with(self, other: TileType) -> bool {
         if self == other {
             return true;
         }
         match self {
-            Self::Sword | Self::Enemy | Self::Special => match other {
-                Self::Sword | Self::Enemy | Self::Special => true,
-                _ => false,
-            },
+            Self::Sword | Self::Enemy | Self::Special => {
+                matches!(other, Self::Sword | Self::Enemy | Self::Special)
+            }
             _ => false,
         }
     }
 }
 
-#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum TileInfo {
     Enemy(Being),
     Special(Special),
     None,
 }
 
-impl From<(TileType, &Being, &mut SpecialGenerator)> for TileInfo {
-    fn from(value: (TileType, &Being, &mut SpecialGenerator)) -> Self {
+impl From<(TileType, &Being, &mut SpecialGenerator, &mut GameRng)> for TileInfo {
+    fn from(value: (TileType, &Being, &mut SpecialGenerator, &mut GameRng)) -> Self {
         match value.0 {
             TileType::Potion | TileType::Shield | TileType::Coin | TileType::Sword => Self::None,
             TileType::Enemy => Self::Enemy(*value.1),
-            TileType::Special => Self::Special(value.2.get()),
+            TileType::Special => Self::Special(value.2.get(value.3)),
             TileType::None | TileType::COUNT => unreachable!(""),
         }
     }
@@ -201,8 +188,13 @@ impl TileInfo {
     }
 }
 
-#[derive(Copy, Clone)]
+// assigned in the order tiles are created on a board, and kept by a tile as it moves
+pub type TileId = usize;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Tile {
+    pub id: TileId,
     pub tile_type: TileType,
     pub tile_info: TileInfo,
     pub next_selection: Wind8,
@@ -211,6 +203,7 @@ pub struct Tile {
 impl Default for Tile {
     fn default() -> Tile {
         Tile {
+            id: 0,
             tile_type: TileType::None,
             tile_info: TileInfo::None,
             next_selection: Wind8::None,
@@ -220,8 +213,9 @@ impl Default for Tile {
 
 pub type Destroyed = bool;
 impl Tile {
-    pub fn new(tile_type: TileType, tile_info: TileInfo) -> Tile {
+    pub fn new(id: TileId, tile_type: TileType, tile_info: TileInfo) -> Tile {
         Tile {
+            id,
             tile_type,
             tile_info,
             next_selection: Wind8::None,
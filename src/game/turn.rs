@@ -0,0 +1,45 @@
+use std::fmt;
+
+// a turn runs through these in order:
+//   AwaitingSelection -> ChoosingImprovement -> EnemyPhase -> Refill -> SpecialsPhase
+// and back to AwaitingSelection, or into GameOver once the player dies. the board is
+// refilled before specials run their end of turn effects so that they always act on
+// a full board (and newly spawned specials get their end of turn right away)
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum TurnPhase {
+    AwaitingSelection,
+    ChoosingImprovement,
+    EnemyPhase,
+    Refill,
+    SpecialsPhase,
+    GameOver,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TurnOutcome {
+    // whether the submitted selection was long enough to be collected
+    pub hit: bool,
+    // the phase the game stopped in, either waiting on the player or GameOver
+    pub phase: TurnPhase,
+    // the board was left without any legal paths and got a free reshuffle
+    pub board_reshuffled: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct WrongPhase {
+    pub expected: TurnPhase,
+    pub actual: TurnPhase,
+}
+
+impl fmt::Display for WrongPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "action is only allowed during {:?}, but the game is in {:?}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for WrongPhase {}
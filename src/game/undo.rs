@@ -0,0 +1,277 @@
+use crate::game::error::{GameError, UndoError};
+use crate::game::event::GameEvent;
+use crate::game::replay::GameAction;
+use crate::game::Game;
+use std::collections::VecDeque;
+
+// a game from before a turn, without its action log, and how long that log was
+pub(crate) struct Snapshot {
+    game: Game,
+    action_log_len: usize,
+}
+
+// whole game snapshots, taken right before each turn that hits. restoring one brings
+// back the rng too, so playing an undone turn again gives the same refill and spawns
+#[derive(Default)]
+pub struct UndoHistory {
+    // oldest first
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    // the actions of every undone turn, oldest first, for redo to put back
+    redo_actions: Vec<GameAction>,
+}
+
+impl UndoHistory {
+    fn push(&mut self, snapshot: Snapshot, limit: usize) {
+        self.clear_redo();
+        while !self.undo.is_empty() && self.undo.len() >= limit {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(snapshot);
+    }
+
+    fn clear_redo(&mut self) {
+        self.redo.clear();
+        self.redo_actions.clear();
+    }
+}
+
+impl Game {
+    // called right before a selection is dropped, only kept if it hit
+    pub(crate) fn snapshot_for_undo(&self) -> Option<Snapshot> {
+        if self.config.undo_limit() == 0 {
+            None
+        } else {
+            Some(Snapshot {
+                game: self.clone_without_action_log(),
+                action_log_len: self.action_log.len(),
+            })
+        }
+    }
+
+    pub(crate) fn push_undo_snapshot(&mut self, snapshot: Snapshot) {
+        let limit = self.config.undo_limit();
+        self.history.push(snapshot, limit);
+    }
+
+    // any new action makes the undone turns stale, see Game::log_action
+    pub(crate) fn clear_redo(&mut self) {
+        self.history.clear_redo();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.history.redo.is_empty()
+    }
+
+    // puts the game back to right before the most recent turn was submitted, including
+    // the selection it was submitted with. abilities cast before that stay cast. the
+    // action log is rolled back with it, so a Replay of it still ends up here
+    pub fn undo(&mut self) -> Result<(), GameError> {
+        if self.config.undo_limit() == 0 {
+            return Err(UndoError::Disabled.into());
+        }
+        let snapshot = self
+            .history
+            .undo
+            .pop_back()
+            .ok_or(UndoError::NothingToUndo)?;
+        let mut action_log = std::mem::take(&mut self.action_log);
+        let undone_len = action_log.len();
+        let mut undone_actions = action_log.split_off(snapshot.action_log_len);
+        undone_actions.append(&mut self.history.redo_actions);
+        self.history.redo_actions = undone_actions;
+
+        let undone = self.restore(snapshot.game);
+        self.action_log = action_log;
+        self.history.redo.push(Snapshot {
+            game: undone,
+            action_log_len: undone_len,
+        });
+        self.events.push(GameEvent::TurnUndone);
+        Ok(())
+    }
+
+    // only possible until the next action is taken, even just selecting a tile
+    pub fn redo(&mut self) -> Result<(), GameError> {
+        if self.config.undo_limit() == 0 {
+            return Err(UndoError::Disabled.into());
+        }
+        let snapshot = self.history.redo.pop().ok_or(UndoError::NothingToRedo)?;
+        let mut action_log = std::mem::take(&mut self.action_log);
+        let redone_len = action_log.len();
+        // logging an action clears the redo history, so the log is as undo left it
+        debug_assert!(redone_len + self.history.redo_actions.len() >= snapshot.action_log_len);
+        action_log.extend(
+            self.history
+                .redo_actions
+                .drain(..snapshot.action_log_len - redone_len),
+        );
+
+        let redone = self.restore(snapshot.game);
+        self.action_log = action_log;
+        self.history.undo.push_back(Snapshot {
+            game: redone,
+            action_log_len: redone_len,
+        });
+        self.events.push(GameEvent::TurnRedone);
+        Ok(())
+    }
+
+    // swaps in the snapshot, keeping the history and events, and returns a snapshot
+    // of what was replaced
+    fn restore(&mut self, mut snapshot: Game) -> Game {
+        std::mem::swap(&mut self.history, &mut snapshot.history);
+        std::mem::swap(&mut self.events, &mut snapshot.events);
+        self.board.take_movements();
+        std::mem::replace(self, snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::config::GameConfig;
+    use crate::game::replay::Replay;
+    use crate::game::tests::testhelp_connected_lines;
+    use crate::game::tile::TilePosition;
+    use crate::game::turn::TurnPhase;
+    use rand::Rng;
+
+    fn testhelp_fingerprint(game: &Game) -> Vec<String> {
+        let mut fingerprint = vec![];
+        for y in 0..game.board_height() {
+            for x in 0..game.board_width() {
+                let tile = game
+                    .get_tile(&TilePosition::new(y as isize, x as isize))
+                    .expect("");
+                fingerprint.push(format!("{} {}", tile.id, tile.tile_type as usize));
+            }
+        }
+        let being = &game.player.being;
+        fingerprint.push(format!(
+            "{} {} {} {:?} {:?} {}",
+            being.hit_points,
+            being.shields,
+            game.turns_passed,
+            game.phase,
+            game.improvement_queue,
+            game.rng.clone().gen::<u64>()
+        ));
+        fingerprint.push(format!("{:?}", game.action_log));
+        fingerprint
+    }
+
+    fn testhelp_game(seed: u64, undo_limit: usize) -> Game {
+        Game::new(
+            GameConfig::builder()
+                .seed(seed)
+                .undo_limit(undo_limit)
+                .build()
+                .expect(""),
+        )
+    }
+
+    fn testhelp_select_line(game: &mut Game) {
+        let line = testhelp_connected_lines(game)[0];
+        for tile_position in line.iter() {
+            game.select_tile(tile_position).expect("");
+        }
+    }
+
+    // submits the selection, choosing the first improvements offered
+    fn testhelp_submit(game: &mut Game) {
+        game.submit_turn().expect("");
+        while let Some(set) = game.improvement_choice_set() {
+            let indeces: Vec<usize> = (0..set.num_to_choose).collect();
+            game.choose_improvements(&indeces).expect("");
+        }
+    }
+
+    fn testhelp_play_turn(game: &mut Game) {
+        testhelp_select_line(game);
+        testhelp_submit(game);
+    }
+
+    #[test]
+    fn test_undo_redo_turn() {
+        let mut game = testhelp_game(3, 10);
+        testhelp_play_turn(&mut game);
+        testhelp_select_line(&mut game);
+        let before = testhelp_fingerprint(&game);
+        testhelp_submit(&mut game);
+        let after = testhelp_fingerprint(&game);
+        assert_ne!(before, after);
+        game.take_events();
+        // the action log lives only in the game, never in its snapshots
+        assert!(game
+            .history
+            .undo
+            .iter()
+            .all(|snapshot| snapshot.game.action_log.is_empty()));
+
+        game.undo().expect("");
+        assert_eq!(game.phase(), TurnPhase::AwaitingSelection);
+        assert_eq!(game.selection_path().len(), 3);
+        assert_eq!(testhelp_fingerprint(&game), before);
+        assert_eq!(game.take_events(), vec![GameEvent::TurnUndone]);
+        assert!(game.take_tile_movements().is_empty());
+
+        // the same turn plays out the same way since the rng was rolled back too
+        testhelp_submit(&mut game);
+        assert_eq!(testhelp_fingerprint(&game), after);
+        assert!(!game.can_redo());
+
+        game.undo().expect("");
+        game.redo().expect("");
+        assert_eq!(testhelp_fingerprint(&game), after);
+        assert_eq!(game.redo(), Err(GameError::Undo(UndoError::NothingToRedo)));
+
+        game.undo().expect("");
+        game.undo().expect("");
+        assert_eq!(game.undo(), Err(GameError::Undo(UndoError::NothingToUndo)));
+        game.redo().expect("");
+        game.redo().expect("");
+        assert_eq!(testhelp_fingerprint(&game), after);
+    }
+
+    #[test]
+    fn test_acting_after_undo_clears_redo() {
+        let mut game = testhelp_game(3, 10);
+        testhelp_play_turn(&mut game);
+        testhelp_play_turn(&mut game);
+        game.undo().expect("");
+        assert!(game.can_redo());
+
+        game.cancel_selection().expect("");
+        assert!(!game.can_redo());
+        testhelp_select_line(&mut game);
+        assert_eq!(game.redo(), Err(GameError::Undo(UndoError::NothingToRedo)));
+
+        // the undone turn's actions are gone from the log, so a replay of it ends up here
+        let mut replay = Replay::from_game(&game);
+        let replayed = replay.run_to_end().expect("");
+        assert_eq!(replayed.state_hash(), game.state_hash());
+        assert_eq!(replayed.action_log(), game.action_log());
+    }
+
+    #[test]
+    fn test_undo_limit() {
+        let mut game = testhelp_game(4, 2);
+        for _ in 0..4 {
+            testhelp_play_turn(&mut game);
+        }
+        game.undo().expect("");
+        game.undo().expect("");
+        assert!(!game.can_undo());
+        assert_eq!(game.turns_passed, 2);
+
+        // undo is off unless a config asks for it
+        let mut game = Game::with_seed(4);
+        testhelp_play_turn(&mut game);
+        assert_eq!(game.undo(), Err(GameError::Undo(UndoError::Disabled)));
+    }
+}